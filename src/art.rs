@@ -1,7 +1,9 @@
 use serde::{Deserialize, Serialize};
 use strum_macros::EnumString;
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Default)]
+use crate::pixels;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
 pub struct Sprite {
     pub index: u32,
     pub size: SpriteSize,
@@ -26,7 +28,7 @@ impl Sprite {
 
 use strum_macros::Display;
 #[derive(
-    Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Default, EnumString, Display,
+    Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, Default, EnumString, Display,
 )]
 pub enum SpriteSize {
     #[default]
@@ -35,3 +37,61 @@ pub enum SpriteSize {
     InnerBg,
     OuterBg,
 }
+
+// Applied to a sprite both when it's drawn and when it's checked for collisions
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Transform {
+    pub flip_x: bool,
+    pub flip_y: bool,
+    pub rotation: Rotation,
+    pub scale: u32,
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Transform {
+            flip_x: false,
+            flip_y: false,
+            rotation: Rotation::None,
+            scale: 1,
+        }
+    }
+}
+
+impl Transform {
+    pub fn size(self, size: pixels::Size) -> pixels::Size {
+        let size = match self.rotation {
+            Rotation::None | Rotation::Half => size,
+            Rotation::Quarter | Rotation::ThreeQuarters => pixels::Size::new(size.h, size.w),
+        };
+        pixels::Size::new(size.w * self.scale, size.h * self.scale)
+    }
+}
+
+// Clockwise
+#[derive(
+    Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, Default, EnumString, Display,
+)]
+pub enum Rotation {
+    #[default]
+    None,
+    Quarter,
+    Half,
+    ThreeQuarters,
+}
+
+impl Rotation {
+    pub fn quarter_turns(self) -> u32 {
+        match self {
+            Rotation::None => 0,
+            Rotation::Quarter => 1,
+            Rotation::Half => 2,
+            Rotation::ThreeQuarters => 3,
+        }
+    }
+
+    pub fn radians(self) -> f32 {
+        self.quarter_turns() as f32 * std::f32::consts::FRAC_PI_2
+    }
+}
@@ -56,6 +56,21 @@ impl MidiFileContainer {
     }
 }
 
+// The sounds in sounds/ that any game can play without bringing its own
+pub const SOUND_LIBRARY: [&str; 11] = [
+    "WhaleShort",
+    "Whale3",
+    "Struggle",
+    "Coin",
+    "Zap",
+    "Boom",
+    "Jump",
+    "1Up",
+    "Lose",
+    "Hurt",
+    "Blip",
+];
+
 pub struct AudioPlayer {
     pub sink_player: Option<SinkPlayer>,
     pub live_player: LivePlayer,
@@ -69,61 +84,11 @@ impl AudioPlayer {
                 let sink_music = Sink::try_new(&stream_handle)?;
                 log::debug!("WORKED HERE?");
 
-                // TODO: !!!
                 let mut temp_loaded_sounds = HashMap::new();
-                let data = macroquad::file::load_file("sounds/WhaleShort.ogg")
-                    .await
-                    .unwrap();
-                temp_loaded_sounds.insert("WhaleShort".to_owned(), data);
-                let data = macroquad::file::load_file("sounds/Whale3.ogg")
-                    .await
-                    .unwrap();
-                temp_loaded_sounds.insert("Whale3".to_owned(), data);
-                // TODO: !!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!
-                temp_loaded_sounds.insert(
-                    "Struggle".to_owned(),
-                    macroquad::file::load_file("sounds/Struggle.ogg")
-                        .await
-                        .unwrap(),
-                );
-                temp_loaded_sounds.insert(
-                    "Struggle".to_owned(),
-                    macroquad::file::load_file("sounds/Struggle.ogg")
-                        .await
-                        .unwrap(),
-                );
-                temp_loaded_sounds.insert(
-                    "Coin".to_owned(),
-                    macroquad::file::load_file("sounds/Coin.ogg").await.unwrap(),
-                );
-                temp_loaded_sounds.insert(
-                    "Zap".to_owned(),
-                    macroquad::file::load_file("sounds/Zap.ogg").await.unwrap(),
-                );
-                temp_loaded_sounds.insert(
-                    "Boom".to_owned(),
-                    macroquad::file::load_file("sounds/Boom.ogg").await.unwrap(),
-                );
-                temp_loaded_sounds.insert(
-                    "Jump".to_owned(),
-                    macroquad::file::load_file("sounds/Jump.ogg").await.unwrap(),
-                );
-                temp_loaded_sounds.insert(
-                    "1Up".to_owned(),
-                    macroquad::file::load_file("sounds/1Up.ogg").await.unwrap(),
-                );
-                temp_loaded_sounds.insert(
-                    "Lose".to_owned(),
-                    macroquad::file::load_file("sounds/Lose.ogg").await.unwrap(),
-                );
-                temp_loaded_sounds.insert(
-                    "Hurt".to_owned(),
-                    macroquad::file::load_file("sounds/Hurt.ogg").await.unwrap(),
-                );
-                temp_loaded_sounds.insert(
-                    "Blip".to_owned(),
-                    macroquad::file::load_file("sounds/Blip.ogg").await.unwrap(),
-                );
+                for name in SOUND_LIBRARY {
+                    let data = macroquad::file::load_file(&format!("sounds/{}.ogg", name)).await?;
+                    temp_loaded_sounds.insert(name.to_owned(), data);
+                }
 
                 Some(SinkPlayer {
                     music_sink: sink_music,
@@ -1,4 +1,5 @@
 use super::pixels::{Position, Rect, Size};
+use std::collections::HashMap;
 
 fn is_in_range(x: i32, y: i32, width: i32, height: i32) -> bool {
     if x < 0 || y < 0 {
@@ -70,6 +71,146 @@ pub fn is_adjusted_subsection_square_active<G: Grid>(
     grid.is_square_active(offset)
 }
 
+// A copy of which squares in a section are active, so the source doesn't need reading again
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Mask {
+    width: i32,
+    height: i32,
+    bits: Vec<bool>,
+}
+
+impl Mask {
+    pub fn from_section<G: Grid>(grid: &G, section: Rect) -> Mask {
+        let width = section.width() as i32;
+        let height = section.height() as i32;
+        let mut bits = Vec::with_capacity((width * height) as usize);
+        for y in 0..height {
+            for x in 0..width {
+                bits.push(grid.is_square_active(section.min + Position::new(x, y)));
+            }
+        }
+        Mask {
+            width,
+            height,
+            bits,
+        }
+    }
+
+    pub fn section(&self) -> Rect {
+        Rect::aabb(0, 0, self.width, self.height)
+    }
+
+    pub fn flipped(&self, flip_x: bool, flip_y: bool) -> Mask {
+        self.remapped(self.width, self.height, |x, y| {
+            let x = if flip_x { self.width - 1 - x } else { x };
+            let y = if flip_y { self.height - 1 - y } else { y };
+            Position::new(x, y)
+        })
+    }
+
+    // Clockwise
+    pub fn rotated(&self, quarter_turns: u32) -> Mask {
+        (0..quarter_turns % 4).fold(self.clone(), |mask, _| {
+            mask.remapped(mask.height, mask.width, |x, y| {
+                Position::new(y, mask.height - 1 - x)
+            })
+        })
+    }
+
+    pub fn scaled(&self, scale: u32) -> Mask {
+        let scale = scale.max(1) as i32;
+        self.remapped(self.width * scale, self.height * scale, |x, y| {
+            Position::new(x / scale, y / scale)
+        })
+    }
+
+    // Each square in the new mask takes its value from a square in this one
+    fn remapped<F>(&self, width: i32, height: i32, source: F) -> Mask
+    where
+        F: Fn(i32, i32) -> Position,
+    {
+        let mut bits = Vec::with_capacity((width * height) as usize);
+        for y in 0..height {
+            for x in 0..width {
+                bits.push(self.get_square_bit(source(x, y)));
+            }
+        }
+        Mask {
+            width,
+            height,
+            bits,
+        }
+    }
+}
+
+impl Grid for Mask {
+    fn size(&self) -> (i32, i32) {
+        (self.width, self.height)
+    }
+
+    fn get_square_bit(&self, position: Position) -> bool {
+        self.bits[(position.y * self.width + position.x) as usize]
+    }
+}
+
+// Where a section ends up once centred on a position
+pub fn centred_bounds(position: Position, size: Size) -> Rect {
+    let left = position.x - (size.w / 2) as i32;
+    let top = position.y - (size.h / 2) as i32;
+    Rect::tlwh(left, top, size.w, size.h)
+}
+
+// Buckets rects by the cells they cover so only rects sharing a cell get compared
+pub struct SpatialHash {
+    cell_size: i32,
+    rects: Vec<Rect>,
+    cells: HashMap<(i32, i32), Vec<usize>>,
+}
+
+impl SpatialHash {
+    pub fn new(cell_size: i32, rects: Vec<Rect>) -> SpatialHash {
+        let mut spatial_hash = SpatialHash {
+            cell_size,
+            rects,
+            cells: HashMap::new(),
+        };
+        for (index, rect) in spatial_hash.rects.iter().enumerate() {
+            for cell in spatial_hash.cells_covered(*rect) {
+                spatial_hash.cells.entry(cell).or_default().push(index);
+            }
+        }
+        spatial_hash
+    }
+
+    // Indices of the other rects that collide with the one at index, in order
+    pub fn overlapping(&self, index: usize) -> Vec<usize> {
+        let rect = self.rects[index];
+        let mut found: Vec<usize> = self
+            .cells_covered(rect)
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .copied()
+            .filter(|&other| other != index && self.rects[other].collides(rect))
+            .collect();
+        found.sort_unstable();
+        found.dedup();
+        found
+    }
+
+    fn cells_covered(&self, rect: Rect) -> impl Iterator<Item = (i32, i32)> {
+        let is_empty = rect.width() == 0 || rect.height() == 0;
+        let min_x = rect.min.x.div_euclid(self.cell_size);
+        let min_y = rect.min.y.div_euclid(self.cell_size);
+        let max_x = (rect.max.x - 1).div_euclid(self.cell_size);
+        let max_y = if is_empty {
+            min_y - 1
+        } else {
+            (rect.max.y - 1).div_euclid(self.cell_size)
+        };
+        (min_y..=max_y).flat_map(move |y| (min_x..=max_x).map(move |x| (x, y)))
+    }
+}
+
 pub struct GridSection<'a, G> {
     pub section: Rect,
     pub grid: &'a G,
@@ -100,7 +241,14 @@ impl<'a, G: Grid> CollisionObject<'a, G> {
         is_adjusted_subsection_square_active(self.grid, position, self.position, self.section)
     }
 
+    pub fn bounds(&self) -> Rect {
+        centred_bounds(self.position, self.section.size())
+    }
+
     pub fn collides_with_rect(&self, rect: Rect) -> bool {
+        let Some(rect) = self.bounds().intersecting_rect(rect) else {
+            return false;
+        };
         for x in rect.min.x..rect.max.x {
             for y in rect.min.y..rect.max.y {
                 if self.is_square_active(Position::new(x, y)) {
@@ -111,14 +259,10 @@ impl<'a, G: Grid> CollisionObject<'a, G> {
         false
     }
 
-    // TODO: Boptimise
-    pub fn collides_with_other(&self, obj: CollisionObject<'a, G>) -> bool {
-        let region_to_check = Rect::xywh(
-            self.position.x as f32,
-            self.position.y as f32,
-            self.section.width(),
-            self.section.height(),
-        );
+    pub fn collides_with_other<H: Grid>(&self, obj: CollisionObject<H>) -> bool {
+        let Some(region_to_check) = self.bounds().intersecting_rect(obj.bounds()) else {
+            return false;
+        };
         for x in region_to_check.min.x..region_to_check.max.x {
             for y in region_to_check.min.y..region_to_check.max.y {
                 let position = Position::new(x, y);
@@ -192,6 +336,80 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_mask() {
+        let section = Rect::aabb(1, 1, 4, 4);
+        let mask = Mask::from_section(&GRID, section);
+        for x in 0..3 {
+            for y in 0..3 {
+                let position = Position::new(x, y);
+                assert_eq!(
+                    mask.is_square_active(position),
+                    GRID.is_square_active(position + section.min)
+                );
+            }
+        }
+        assert!(!mask.is_square_active(Position::new(3, 3)));
+
+        let a = CollisionObject {
+            position: Position::new(10, 10),
+            section: mask.section(),
+            grid: &mask,
+        };
+        let touching = CollisionObject {
+            position: Position::new(11, 12),
+            ..a
+        };
+        let interleaved = CollisionObject {
+            position: Position::new(11, 10),
+            ..a
+        };
+        assert!(a.collides_with_rect(Rect::aabb(0, 0, 11, 10)));
+        assert!(!a.collides_with_rect(Rect::aabb(0, 0, 10, 10)));
+        assert!(a.collides_with_other(touching));
+        assert!(!a.collides_with_other(interleaved));
+    }
+
+    #[test]
+    fn test_transformed_mask() {
+        let mask = Mask::from_section(&GRID, Rect::aabb(0, 0, 3, 2));
+
+        let flipped = mask.flipped(true, false);
+        assert!(flipped.is_square_active(Position::new(2, 0)));
+        assert!(flipped.is_square_active(Position::new(0, 1)));
+        assert!(!flipped.is_square_active(Position::new(0, 0)));
+
+        let rotated = mask.rotated(1);
+        assert_eq!(rotated.size(), (2, 3));
+        assert!(rotated.is_square_active(Position::new(1, 0)));
+        assert!(rotated.is_square_active(Position::new(0, 2)));
+        assert_eq!(mask.rotated(4), mask);
+
+        let scaled = mask.scaled(2);
+        assert_eq!(scaled.size(), (6, 4));
+        assert!(scaled.is_square_active(Position::new(1, 1)));
+        assert!(scaled.is_square_active(Position::new(5, 3)));
+        assert!(!scaled.is_square_active(Position::new(2, 1)));
+    }
+
+    #[test]
+    fn test_spatial_hash() {
+        let rects = vec![
+            Rect::aabb(0, 0, 10, 10),
+            Rect::aabb(5, 5, 40, 15),
+            Rect::aabb(35, -10, 50, 6),
+            Rect::aabb(100, 100, 110, 110),
+            Rect::aabb(2, 2, 2, 2),
+        ];
+        let spatial_hash = SpatialHash::new(16, rects);
+
+        assert_eq!(spatial_hash.overlapping(0), vec![1]);
+        assert_eq!(spatial_hash.overlapping(1), vec![0, 2]);
+        assert_eq!(spatial_hash.overlapping(2), vec![1]);
+        assert!(spatial_hash.overlapping(3).is_empty());
+        assert!(spatial_hash.overlapping(4).is_empty());
+    }
+
     #[test]
     fn test_adjusted_subsection() {
         #[rustfmt::skip]
@@ -11,6 +11,32 @@ pub enum Speed {
     VeryFast,
 }
 
+impl Speed {
+    // Anything past very fast stays very fast
+    pub fn faster_by(self, steps: usize) -> Speed {
+        const SPEEDS: [Speed; 5] = [
+            Speed::VerySlow,
+            Speed::Slow,
+            Speed::Normal,
+            Speed::Fast,
+            Speed::VeryFast,
+        ];
+        let index = SPEEDS.iter().position(|&speed| speed == self).unwrap_or(2);
+        SPEEDS[(index + steps).min(SPEEDS.len() - 1)]
+    }
+
+    // Each step up doubles how far something moves in a frame
+    pub fn multiplier(self) -> f32 {
+        match self {
+            Speed::VerySlow => 0.25,
+            Speed::Slow => 0.5,
+            Speed::Normal => 1.0,
+            Speed::Fast => 2.0,
+            Speed::VeryFast => 4.0,
+        }
+    }
+}
+
 impl std::fmt::Display for Speed {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let s = match self {
@@ -9,7 +9,7 @@ use crate::history::{Event, Step, StepDirection};
 use crate::inp::Input;
 use crate::meta::{self, Environment, INNER_HEIGHT, INNER_WIDTH, OUTER_HEIGHT, OUTER_WIDTH};
 use crate::play::{self, is_position_in_sized_area, Assets, Size};
-use crate::rend::{Image, Texture};
+use crate::rend::{headless_texture, Image, Texture};
 use itertools::Itertools;
 use macroquad::ui::{self, hash};
 use macroquad::{
@@ -250,6 +250,20 @@ impl DrawTool {
         }
     }
 
+    // No paints, only enough to keep update_game happy without a window
+    pub fn headless() -> DrawTool {
+        let image = Image::gen_image_color(PAINT_SIZE, PAINT_SIZE, colours::WHITE);
+
+        DrawTool {
+            paint_choices: Vec::new(),
+            erase_paint: EditableImage {
+                image,
+                texture: headless_texture(),
+            },
+            tracker: Tracker::default(),
+        }
+    }
+
     pub fn draw_stuff(
         &mut self,
         environment: &mut Environment,
@@ -22,10 +22,20 @@ const INNER_SIZE: pixels::Size = pixels::Size::new(INNER_WIDTH, INNER_HEIGHT);
 pub const SPRITESHEET_WIDTH: u32 = 512;
 
 #[derive(Clone, Copy, Debug, PartialEq)]
+// Scroll is the world position shown in the top left, it only moves in worlds bigger than the screen
 pub enum Camera {
     Outer,
-    Inner { position: pixels::Position },
-    EditedOuter { position: pixels::Position },
+    Inner {
+        position: pixels::Position,
+        scroll: pixels::Position,
+    },
+    EditedOuter {
+        position: pixels::Position,
+        scroll: pixels::Position,
+    },
+    ScrolledOuter {
+        scroll: pixels::Position,
+    },
 }
 
 impl Camera {
@@ -34,7 +44,14 @@ impl Camera {
         let screen_height = quad_window::screen_height();
         let ratio = screen_width / screen_height;
         let intended_ratio = 16.0 / 9.0;
-        let outer_rect = || Rect::new(0.0, 0.0, OUTER_WIDTH as f32, OUTER_HEIGHT as f32);
+        let outer_rect = |scroll: pixels::Position| {
+            Rect::new(
+                scroll.x as f32,
+                scroll.y as f32,
+                OUTER_WIDTH as f32,
+                OUTER_HEIGHT as f32,
+            )
+        };
         let scale_to_ratio = |camera: &mut QuadCamera| {
             if ratio > intended_ratio {
                 camera.zoom.x /= ratio / intended_ratio;
@@ -42,6 +59,12 @@ impl Camera {
                 camera.zoom.y *= ratio / intended_ratio;
             }
         };
+        let outside_camera = |scroll: pixels::Position| {
+            let mut camera = QuadCamera::from_display_rect(outer_rect(scroll));
+            camera.zoom.y *= -1.0;
+            scale_to_ratio(&mut camera);
+            camera
+        };
         let inside_camera = |rect: Rect, position: pixels::Position| {
             let mut camera = QuadCamera::from_display_rect(rect);
             {
@@ -66,17 +89,18 @@ impl Camera {
             camera
         };
         match self {
-            Camera::Outer => {
-                let mut camera = QuadCamera::from_display_rect(outer_rect());
-                camera.zoom.y *= -1.0;
-                scale_to_ratio(&mut camera);
-                camera
-            }
-            Camera::Inner { position } => {
-                let rect = Rect::new(0.0, 0.0, INNER_WIDTH as f32, INNER_HEIGHT as f32);
+            Camera::Outer => outside_camera(pixels::Position::default()),
+            Camera::ScrolledOuter { scroll } => outside_camera(scroll),
+            Camera::Inner { position, scroll } => {
+                let rect = Rect::new(
+                    scroll.x as f32,
+                    scroll.y as f32,
+                    INNER_WIDTH as f32,
+                    INNER_HEIGHT as f32,
+                );
                 inside_camera(rect, position)
             }
-            Camera::EditedOuter { position } => inside_camera(outer_rect(), position),
+            Camera::EditedOuter { position, scroll } => inside_camera(outer_rect(scroll), position),
         }
     }
 }
@@ -110,12 +134,21 @@ impl Drawer {
     pub fn clear(&mut self, camera: Camera, colour: Colour) {
         self.set_camera(camera);
 
-        let (width, height) = match camera {
-            Camera::Outer | Camera::EditedOuter { .. } => (OUTER_WIDTH, OUTER_HEIGHT),
-            Camera::Inner { .. } => (INNER_WIDTH, INNER_HEIGHT),
+        let (width, height, scroll) = match camera {
+            Camera::Outer => (OUTER_WIDTH, OUTER_HEIGHT, pixels::Position::default()),
+            Camera::EditedOuter { scroll, .. } | Camera::ScrolledOuter { scroll } => {
+                (OUTER_WIDTH, OUTER_HEIGHT, scroll)
+            }
+            Camera::Inner { scroll, .. } => (INNER_WIDTH, INNER_HEIGHT, scroll),
         };
 
-        draw_rectangle(0.0, 0.0, width as f32, height as f32, colour);
+        draw_rectangle(
+            scroll.x as f32,
+            scroll.y as f32,
+            width as f32,
+            height as f32,
+            colour,
+        );
     }
 
     pub fn draw_debug_line(
@@ -390,6 +423,9 @@ impl Drawer {
                     screen_rect.width() as f32,
                     screen_rect.height() as f32,
                 )),
+                rotation: params.rotation,
+                flip_x: params.flip_x,
+                flip_y: params.flip_y,
                 ..Default::default()
             },
         );
@@ -626,6 +662,10 @@ pub struct DrawParams {
     pub source: Option<pixels::Rect>,
     pub thickness: u32,
     pub scale: Option<f32>,
+    pub flip_x: bool,
+    pub flip_y: bool,
+    // Clockwise around the centre
+    pub rotation: f32,
 }
 
 impl Default for DrawParams {
@@ -635,6 +675,9 @@ impl Default for DrawParams {
             source: None,
             thickness: 1,
             scale: None,
+            flip_x: false,
+            flip_y: false,
+            rotation: 0.0,
         }
     }
 }
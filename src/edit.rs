@@ -16,8 +16,8 @@ use super::play;
 use super::play::Text;
 use super::serial;
 use super::serial::{
-    CollisionWith, Demand, Direction, Hover, JumpLocation, Motion, Question, Switch, When,
-    WhichButton, WinStatus,
+    CollisionWith, Demand, Direction, Group, Hover, JumpLocation, Motion, ParticleStyle, Question,
+    Switch, When, WhichButton, WinStatus,
 };
 use macroquad::{color::Color as Colour, math::Vec2, texture::Texture2D};
 use regex::Regex;
@@ -40,6 +40,24 @@ pub struct Editor {
     pub redo_stack: Vec<history::Step>,
     pub inner_copy: Option<play::Game>,
     pub paused_copy: Option<play::Game>,
+    // The difficulty chosen before playing at another one, put back when the subgame stops
+    pub difficulty_before_preview: Option<String>,
+}
+
+impl Editor {
+    pub fn selected_name(&self, members: &[play::Member]) -> Option<String> {
+        members
+            .get(self.selected_index)
+            .map(|member| member.name.clone())
+    }
+
+    // Members destroyed while the subgame plays are removed and the rest move down,
+    // so the same member is selected again, or the first one if it's gone
+    pub fn reselect(&mut self, members: &[play::Member], name: Option<String>) {
+        self.selected_index = name
+            .and_then(|name| members.iter().position(|member| member.name == name))
+            .unwrap_or(0);
+    }
 }
 
 #[derive(Debug, Clone, Default)]
@@ -173,6 +191,7 @@ pub fn question_from_context(context_variables: &HashMap<String, String>) -> Que
                             *name = member_name.to_owned();
                         }
                     }
+                    _ => {}
                 }
             }
         }
@@ -223,6 +242,38 @@ pub fn question_from_context(context_variables: &HashMap<String, String>) -> Que
     question
 }
 
+// Tints are shown as "#rrggbb"
+pub fn colour_hex(colour: serial::Colour) -> String {
+    let channel = |c: f32| (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+    format!(
+        "#{:02x}{:02x}{:02x}",
+        channel(colour.r),
+        channel(colour.g),
+        channel(colour.b)
+    )
+}
+
+// Paths are kept in the context as "x,y;x,y"
+pub fn path_from_text(text: &str) -> Vec<pixels::Position> {
+    text.split(';')
+        .filter_map(|point| {
+            let (x, y) = point.split_once(',')?;
+            Some(pixels::Position::new(
+                x.trim().parse().ok()?,
+                y.trim().parse().ok()?,
+            ))
+        })
+        .collect()
+}
+
+pub fn path_text(points: &[pixels::Position]) -> String {
+    points
+        .iter()
+        .map(|point| format!("{},{}", point.x, point.y))
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
 pub fn demand_from_context(
     context_variables: &HashMap<String, String>,
     animation_scratch: &[Sprite],
@@ -380,6 +431,21 @@ pub fn demand_from_context(
                     point.y = get_typed_variable(context_variables, "Y").unwrap_or_default();
                     *speed = get_typed_variable(context_variables, "Speed").unwrap_or_default();
                 }
+                Motion::FollowPath {
+                    points,
+                    speed,
+                    looping,
+                } => {
+                    *points = path_from_text(
+                        context_variables
+                            .get("Path")
+                            .map(String::as_str)
+                            .unwrap_or_default(),
+                    );
+                    *speed = get_typed_variable(context_variables, "Speed").unwrap_or_default();
+                    *looping =
+                        get_typed_variable(context_variables, "Path Looping").unwrap_or_default();
+                }
                 Motion::AttachFromPositions { name } => {
                     if let Some(member_name) = context_variables.get("Member Name") {
                         *name = member_name.to_owned();
@@ -519,6 +585,72 @@ pub fn offset_for_page(page: isize, max_per_page: usize, len: usize) -> usize {
     (page * max_per_page as isize).rem_euclid((len / max_per_page * max_per_page) as isize) as usize
 }
 
+// Lists that can grow (chores, questions, demands) have an extra slot at the end for "New"
+pub fn growable_index(
+    members: &[play::Member],
+    var: &str,
+    page: isize,
+    len: usize,
+    slot: usize,
+) -> usize {
+    let max_per_page = max_var_per_page(members, var);
+    if max_per_page == 0 {
+        return slot.saturating_sub(1);
+    }
+    let len = padded_len(len + 1, max_per_page);
+    (offset_for_page(page, max_per_page, len) + slot).saturating_sub(1)
+}
+
+// The length of the chore, question or demand list being edited
+pub fn growable_len(
+    name: &str,
+    subgame: &play::Game,
+    editor: &Editor,
+    context_variables: &HashMap<String, String>,
+) -> Option<usize> {
+    let todo_list = &subgame.members.get(editor.selected_index)?.todo_list;
+    match name {
+        "Chore" => Some(todo_list.len()),
+        "Question" => Some(
+            play::chore(todo_list, chore_index_from_context(context_variables))
+                .questions
+                .len(),
+        ),
+        "Demand" => Some(
+            play::chore(todo_list, chore_index_from_context(context_variables))
+                .demands
+                .len(),
+        ),
+        _ => None,
+    }
+}
+
+pub fn slot_from_member_text(text: &str, var: &str) -> Option<usize> {
+    text.strip_prefix('{')?
+        .strip_suffix('}')?
+        .strip_prefix(var)?
+        .trim()
+        .parse()
+        .ok()
+}
+
+fn group_text(group: Group) -> &'static str {
+    match group {
+        Group::Any => "any ",
+        Group::All => "all ",
+    }
+}
+
+fn button_state_text(state: Option<Button>) -> &'static str {
+    match state {
+        None => "in any state",
+        Some(Button::Up) => "up",
+        Some(Button::Press) => "pressed",
+        Some(Button::Down) => "held down",
+        Some(Button::Release) => "released",
+    }
+}
+
 // TODO: simple_text for :String too
 pub fn simple_text(text: &str) -> Vec<FancyText> {
     vec![text.plain()]
@@ -588,26 +720,34 @@ impl ToText for &str {
 
 pub fn fancy_question_text_for_list(
     member: &play::Member,
+    editor_members: &[play::Member],
     subgame: &play::Game,
     editor: &Editor,
     context_variables: &HashMap<String, String>,
 ) -> Vec<FancyText> {
     let chore_index = chore_index_from_context(context_variables);
-
-    for i in 0..serial::QUESTION_COUNT {
-        if member.text.contents == format!("{{Question {}}}", i + 1) {
-            let question =
-                &subgame.members[editor.selected_index].todo_list[chore_index].questions[i];
-
-            return match question {
-                Question::None => simple_text("New"),
-                question => {
-                    let mut fancy_text = fancy_question_text(question);
-                    fancy_text.push("?".plain());
-                    fancy_text
-                }
-            };
-        }
+    let chore = play::chore(
+        &subgame.members[editor.selected_index].todo_list,
+        chore_index,
+    );
+
+    if let Some(slot) = slot_from_member_text(&member.text.contents, "Question") {
+        let i = growable_index(
+            editor_members,
+            "Question",
+            editor.page,
+            chore.questions.len(),
+            slot,
+        );
+
+        return match chore.question(i) {
+            Question::None => simple_text("New"),
+            question => {
+                let mut fancy_text = fancy_question_text(&question);
+                fancy_text.push("?".plain());
+                fancy_text
+            }
+        };
     }
 
     Vec::new()
@@ -615,60 +755,45 @@ pub fn fancy_question_text_for_list(
 
 pub fn fancy_question_text_for_chore(
     member: &play::Member,
+    editor_members: &[play::Member],
     subgame: &play::Game,
     editor: &Editor,
 ) -> Vec<FancyText> {
-    for i in 0..serial::CHORE_COUNT {
-        // TODO: Reconfigure to remove clone
-        if member.text.contents == format!("{{Chore {}}}", i + 1) {
-            let question = &subgame.members[editor.selected_index].todo_list[i].questions[0];
-
-            let mut fancy_text = match &question {
-                Question::None => {
-                    if subgame.members[editor.selected_index].todo_list[i].questions
-                        == [
-                            Question::None,
-                            Question::None,
-                            Question::None,
-                            Question::None,
-                            Question::None,
-                            Question::None,
-                        ]
-                        && subgame.members[editor.selected_index].todo_list[i].demands
-                            == [
-                                Demand::None,
-                                Demand::None,
-                                Demand::None,
-                                Demand::None,
-                                Demand::None,
-                                Demand::None,
-                            ]
-                    {
-                        simple_text("New")
-                    } else if subgame.members[editor.selected_index].todo_list[i].questions
-                        == [
-                            Question::None,
-                            Question::None,
-                            Question::None,
-                            Question::None,
-                            Question::None,
-                            Question::None,
-                        ]
-                    {
-                        simple_text("Every Frame")
-                    } else {
-                        simple_text("...")
-                    }
-                }
-                question => fancy_question_text(question),
-            };
+    if let Some(slot) = slot_from_member_text(&member.text.contents, "Chore") {
+        let todo_list = &subgame.members[editor.selected_index].todo_list;
+        let i = growable_index(editor_members, "Chore", editor.page, todo_list.len(), slot);
+        let chore = play::chore(todo_list, i);
+        let question = chore.question(0);
+
+        // Chores for the same state start with it so they can be told apart at a glance
+        let mut fancy_text = match &chore.state {
+            Some(state) => vec![shorten(state, 8).in_colour(colours::GREEN), ": ".plain()],
+            None => Vec::new(),
+        };
+
+        if let Some(label) = &chore.label {
+            fancy_text.extend(simple_text(label));
+            return fancy_text;
+        }
 
-            if *question != Question::None {
-                fancy_text.push("...".plain())
+        fancy_text.extend(match &question {
+            Question::None => {
+                if chore.is_empty() {
+                    simple_text("New")
+                } else if chore.questions.iter().all(|q| *q == Question::None) {
+                    simple_text("Every Frame")
+                } else {
+                    simple_text("...")
+                }
             }
+            question => fancy_question_text(question),
+        });
 
-            return fancy_text;
+        if question != Question::None {
+            fancy_text.push("...".plain())
         }
+
+        return fancy_text;
     }
 
     Vec::new()
@@ -713,6 +838,31 @@ pub fn fancy_question_text(question: &Question) -> Vec<FancyText> {
                     .in_colour(colours::GREEN),
             ]
         }
+        Question::IsTimeAt(When::Every { interval, offset }) => {
+            let mut fancy_text = vec![
+                "Is it every ".plain(),
+                format!("{} frames", interval).in_colour(colours::GREEN),
+            ];
+            if *offset != 0 {
+                fancy_text.push(" after ".plain());
+                fancy_text.push(format!("{} frames", offset).in_colour(colours::GREEN));
+            }
+            fancy_text
+        }
+        Question::IsKeyInteracting { key, state } => {
+            vec![
+                "Is the ".plain(),
+                format!("{:?} key ", key).in_colour(colours::GREEN),
+                button_state_text(*state).in_colour(colours::BLUE),
+            ]
+        }
+        Question::IsControlInteracting { control, state } => {
+            vec![
+                "Is ".plain(),
+                format!("{:?} ", control).in_colour(colours::GREEN),
+                button_state_text(*state).in_colour(colours::BLUE),
+            ]
+        }
         // Anywhere
         Question::IsMouseInteracting {
             which: WhichButton::Left,
@@ -925,6 +1075,38 @@ pub fn fancy_question_text(question: &Question) -> Vec<FancyText> {
                 value.in_colour(colours::BLUE),
             ]
         }
+        Question::IsNumberEqualTo { name, value } => {
+            vec![
+                "Is ".plain(),
+                name.in_colour(colours::RED),
+                " equal to ".plain(),
+                value.to_string().in_colour(colours::BLUE),
+            ]
+        }
+        Question::IsNumberLessThan { name, value } => {
+            vec![
+                "Is ".plain(),
+                name.in_colour(colours::RED),
+                " less than ".plain(),
+                value.to_string().in_colour(colours::BLUE),
+            ]
+        }
+        Question::IsNumberGreaterThan { name, value } => {
+            vec![
+                "Is ".plain(),
+                name.in_colour(colours::RED),
+                " greater than ".plain(),
+                value.to_string().in_colour(colours::BLUE),
+            ]
+        }
+        Question::IsNumberBetween { name, min, max } => {
+            vec![
+                "Is ".plain(),
+                name.in_colour(colours::RED),
+                " between ".plain(),
+                format!("{}-{}", min, max).in_colour(colours::BLUE),
+            ]
+        }
         Question::IsTextSetTo { value } => {
             vec![
                 "Is text".plain(),
@@ -983,7 +1165,53 @@ pub fn fancy_question_text(question: &Question) -> Vec<FancyText> {
                 shorten(name, 12).in_colour(colours::RED),
             ]
         }
+        Question::IsCollidingWith(CollisionWith::Tagged { tag, group }) => {
+            vec![
+                "Has touched ".plain(),
+                group_text(*group).plain(),
+                shorten(tag, 10).in_colour(colours::RED),
+            ]
+        }
+        Question::IsTaggedSwitchSetTo { tag, group, switch } => {
+            vec![
+                "Are ".plain(),
+                group_text(*group).plain(),
+                shorten(tag, 8).in_colour(colours::RED),
+                " switches ".plain(),
+                format!("{:?}", switch).in_colour(colours::GREEN),
+            ]
+        }
         Question::IsAnimationFinished => simple_text("Has animation finished"),
+        Question::IsPathFinished => simple_text("Has path finished"),
+        Question::IsVisible => simple_text("Is visible"),
+        Question::IsInState { state } => {
+            vec![
+                "Is in ".plain(),
+                shorten(state, 16).in_colour(colours::GREEN),
+                " state".plain(),
+            ]
+        }
+        Question::JustEnteredState { state } => {
+            vec![
+                "Just entered ".plain(),
+                shorten(state, 16).in_colour(colours::GREEN),
+                " state".plain(),
+            ]
+        }
+        Question::HasReceived { message } => {
+            vec![
+                "Has received ".plain(),
+                shorten(message, 16).in_colour(colours::AMBER),
+            ]
+        }
+        Question::HasTimerPassed { name, frames } => {
+            vec![
+                "Have ".plain(),
+                format!("{} frames", frames).in_colour(colours::GREEN),
+                " passed since ".plain(),
+                name.in_colour(colours::RED),
+            ]
+        }
         Question::IsPagedVariableValid { name, value } => {
             simple_text(&format!("Is {} {} valid", name, value))
         }
@@ -1002,32 +1230,78 @@ pub fn fancy_question_text(question: &Question) -> Vec<FancyText> {
                 " shortcut used ".plain(),
             ]
         }
+        Question::IsDifficulty(level) => {
+            vec![
+                "Is the difficulty ".plain(),
+                format!("{:?}", level).in_colour(colours::GREEN),
+            ]
+        }
         Question::IsOnWeb => simple_text("Is on web"),
         Question::IsOnDesktop => simple_text("Is on desktop"),
+        Question::Not(question) => {
+            let mut fancy_text = vec!["Not ".in_colour(colours::AMBER)];
+            fancy_text.extend(fancy_grouped_question_text(question));
+            fancy_text
+        }
+        Question::AnyOf(questions) => fancy_question_group_text(questions, " or "),
+        Question::AllOf(questions) => fancy_question_group_text(questions, " and "),
+    }
+}
+
+fn fancy_question_group_text(questions: &[Question], joiner: &str) -> Vec<FancyText> {
+    let mut fancy_text = Vec::new();
+    for (i, question) in questions.iter().enumerate() {
+        if i != 0 {
+            fancy_text.push(joiner.in_colour(colours::AMBER));
+        }
+        fancy_text.extend(fancy_grouped_question_text(question));
+    }
+    fancy_text
+}
+
+// Brackets keep groups inside groups readable
+fn fancy_grouped_question_text(question: &Question) -> Vec<FancyText> {
+    match question {
+        Question::AnyOf(_) | Question::AllOf(_) => {
+            let mut fancy_text = vec!["(".plain()];
+            fancy_text.extend(fancy_question_text(question));
+            fancy_text.push(")".plain());
+            fancy_text
+        }
+        question => fancy_question_text(question),
     }
 }
 
 pub fn fancy_demand_text_for_list(
     member: &play::Member,
+    editor_members: &[play::Member],
     subgame: &play::Game,
     editor: &Editor,
     context_variables: &HashMap<String, String>,
 ) -> Vec<FancyText> {
     let chore_index = chore_index_from_context(context_variables);
-    for i in 0..serial::DEMAND_COUNT {
-        if member.text.contents == format!("{{Demand {}}}", i + 1) {
-            let demand =
-                &subgame.members[editor.selected_index].todo_list[chore_index].demands[i].clone();
-
-            return match demand {
-                Demand::None => simple_text("New"),
-                demand => {
-                    let mut fancy_text = fancy_demand_text(demand);
-                    fancy_text.push("!".plain());
-                    fancy_text
-                }
-            };
-        }
+    let chore = play::chore(
+        &subgame.members[editor.selected_index].todo_list,
+        chore_index,
+    );
+
+    if let Some(slot) = slot_from_member_text(&member.text.contents, "Demand") {
+        let i = growable_index(
+            editor_members,
+            "Demand",
+            editor.page,
+            chore.demands.len(),
+            slot,
+        );
+
+        return match chore.demand(i) {
+            Demand::None => simple_text("New"),
+            demand => {
+                let mut fancy_text = fancy_demand_text(&demand);
+                fancy_text.push("!".plain());
+                fancy_text
+            }
+        };
     }
 
     Vec::new()
@@ -1046,6 +1320,34 @@ pub fn fancy_demand_text(demand: &Demand) -> Vec<FancyText> {
                 FancyText::Sprite(*sprite),
             ]
         }
+        Demand::SetFlip { x, y } => {
+            let flip = match (x, y) {
+                (false, false) => "unflipped",
+                (true, false) => "flipped across",
+                (false, true) => "flipped down",
+                (true, true) => "flipped both ways",
+            };
+            vec!["Draw ".plain(), flip.in_colour(colours::AMBER)]
+        }
+        Demand::SetRotation(rotation) => {
+            vec![
+                "Rotate ".plain(),
+                format!("{:?}", rotation).in_colour(colours::AMBER),
+            ]
+        }
+        Demand::SetScale { scale } => {
+            vec![
+                "Scale by ".plain(),
+                scale.to_string().in_colour(colours::AMBER),
+            ]
+        }
+        Demand::SetTint(tint) => {
+            vec![
+                "Tint ".plain(),
+                colour_hex(*tint).in_colour(Colour::new(tint.r, tint.g, tint.b, 1.0)),
+            ]
+        }
+        Demand::ClearTint => simple_text("Clear tint"),
         Demand::SetSwitch(Switch::On) | Demand::SetSwitch(Switch::SwitchedOn) => {
             vec![
                 "Set ".plain(),
@@ -1062,6 +1364,14 @@ pub fn fancy_demand_text(demand: &Demand) -> Vec<FancyText> {
                 "off".in_colour(colours::BLUE),
             ]
         }
+        Demand::SetTaggedSwitch { tag, switch } => {
+            vec![
+                "Set all ".plain(),
+                shorten(tag, 10).in_colour(colours::RED),
+                " switches ".plain(),
+                format!("{:?}", switch).in_colour(colours::BLUE),
+            ]
+        }
         Demand::SetText(text) => {
             vec![
                 "Set text to ".plain(),
@@ -1086,6 +1396,93 @@ pub fn fancy_demand_text(demand: &Demand) -> Vec<FancyText> {
             }
         }
         Demand::StopAnimation => simple_text("Stop animating"),
+        Demand::Spawn { template, at: _ } => {
+            vec![
+                "Spawn ".in_colour(colours::BLUE),
+                shorten(template, 16).in_colour(colours::RED),
+            ]
+        }
+        Demand::Destroy => {
+            vec![
+                "Destroy ".in_colour(colours::BLUE),
+                "this".in_colour(colours::NULLPURPLE),
+            ]
+        }
+        Demand::EmitParticles { style, count, .. } => {
+            let mut text = vec![
+                "Emit ".in_colour(colours::BLUE),
+                count.to_string().in_colour(colours::AMBER),
+                " ".plain(),
+            ];
+            match style {
+                ParticleStyle::Colour(colour) => {
+                    text.push("particles".in_colour(Colour::new(colour.r, colour.g, colour.b, 1.0)))
+                }
+                ParticleStyle::Sprite(sprite) => {
+                    text.push(FancyText::Sprite(*sprite));
+                    text.push(" particles".plain());
+                }
+            }
+            text
+        }
+        Demand::Show => simple_text("Show"),
+        Demand::Hide => simple_text("Hide"),
+        Demand::BringToFront => simple_text("Bring to front"),
+        Demand::SendToBack => simple_text("Send to back"),
+        Demand::SetLayer { layer } => {
+            vec![
+                "Move to layer ".plain(),
+                layer.to_string().in_colour(colours::AMBER),
+            ]
+        }
+        Demand::CameraFollow { name, .. } => {
+            vec![
+                "Camera ".in_colour(colours::BLUE),
+                "follows ".plain(),
+                shorten(name, 16).in_colour(colours::RED),
+            ]
+        }
+        Demand::CameraPanTo { point, .. } => {
+            vec![
+                "Camera ".in_colour(colours::BLUE),
+                "pans to ".plain(),
+                FancyText::Point(*point),
+            ]
+        }
+        Demand::StartTimer { name } => {
+            vec!["Start ".plain(), name.in_colour(colours::RED)]
+        }
+        Demand::Wait { frames } => {
+            vec![
+                "Wait ".in_colour(colours::BLUE),
+                format!("{} frames", frames).in_colour(colours::GREEN),
+            ]
+        }
+        Demand::CancelSequence => simple_text("Stop waiting chores"),
+        Demand::EnterState { state } => {
+            vec![
+                "Enter ".in_colour(colours::BLUE),
+                shorten(state, 16).in_colour(colours::GREEN),
+                " state".plain(),
+            ]
+        }
+        Demand::Send { message, to } => {
+            let mut text = vec![
+                "Send ".in_colour(colours::BLUE),
+                shorten(message, 16).in_colour(colours::AMBER),
+            ];
+            match to {
+                Some(name) => {
+                    text.push(" to ".plain());
+                    text.push(shorten(name, 16).in_colour(colours::RED));
+                }
+                None => text.push(" to everyone".plain()),
+            }
+            text
+        }
+        Demand::StopTimer { name } => {
+            vec!["Stop ".plain(), name.in_colour(colours::RED)]
+        }
         Demand::PlaySound { name } => {
             vec![
                 "Play ".plain(),
@@ -1121,6 +1518,14 @@ pub fn fancy_demand_text(demand: &Demand) -> Vec<FancyText> {
                 format!(" {:?}", speed).in_colour(colours::AMBER),
             ]
         }
+        Demand::Motion(Motion::FollowPath {
+            points, looping, ..
+        }) => {
+            vec![
+                format!("Follow a path of {} points", points.len()).plain(),
+                format!(" {:?}", looping).in_colour(colours::AMBER),
+            ]
+        }
         Demand::Motion(Motion::JumpTo(JumpLocation::Mouse)) => {
             vec![
                 "Jump ".in_colour(colours::BLUE),
@@ -1149,6 +1554,13 @@ pub fn fancy_demand_text(demand: &Demand) -> Vec<FancyText> {
                 shorten(name, 16).in_colour(colours::RED),
             ]
         }
+        Demand::Motion(Motion::JumpTo(JumpLocation::Tagged { tag })) => {
+            vec![
+                "Jump ".in_colour(colours::BLUE),
+                "to any ".plain(),
+                shorten(tag, 12).in_colour(colours::RED),
+            ]
+        }
         Demand::Motion(Motion::JumpTo(JumpLocation::Relative { offset: _ })) => {
             vec!["Jump ".in_colour(colours::BLUE), "relative".plain()]
         }
@@ -1184,6 +1596,32 @@ pub fn fancy_demand_text(demand: &Demand) -> Vec<FancyText> {
                 shorten(name, 16).in_colour(colours::RED),
             ]
         }
+        Demand::Motion(Motion::TargetTagged { tag, .. }) => {
+            vec![
+                "Target ".in_colour(colours::BLUE),
+                "nearest ".plain(),
+                shorten(tag, 12).in_colour(colours::RED),
+            ]
+        }
+        Demand::Motion(Motion::Physics {
+            gravity, bounds, ..
+        }) => {
+            let mut fancy_text = vec!["Move with physics".in_colour(colours::BLUE)];
+            if *gravity != 0.0 {
+                fancy_text.push(format!(" falling {}", gravity).plain());
+            }
+            if let Some(area) = bounds {
+                fancy_text.push(" in ".plain());
+                fancy_text.push(FancyText::Area(*area));
+            }
+            fancy_text
+        }
+        Demand::Motion(Motion::Impulse { x, y }) => {
+            vec![
+                "Push ".in_colour(colours::BLUE),
+                format!("by {}, {}", x, y).in_colour(colours::GREEN),
+            ]
+        }
         Demand::Motion(Motion::AttachFromPositions { name }) => {
             vec![
                 "Attach".in_colour(colours::BLUE),
@@ -1208,10 +1646,42 @@ pub fn fancy_demand_text(demand: &Demand) -> Vec<FancyText> {
                 value.in_colour(colours::BLUE),
             ]
         }
+        Demand::SetNumber { name, value } => {
+            vec![
+                "Set ".plain(),
+                name.in_colour(colours::RED),
+                " to ".plain(),
+                value.to_string().in_colour(colours::BLUE),
+            ]
+        }
+        Demand::AddToNumber { name, value } => {
+            vec![
+                "Add ".plain(),
+                value.to_string().in_colour(colours::BLUE),
+                " to ".plain(),
+                name.in_colour(colours::RED),
+            ]
+        }
+        Demand::MultiplyNumber { name, value } => {
+            vec![
+                "Multiply ".plain(),
+                name.in_colour(colours::RED),
+                " by ".plain(),
+                value.to_string().in_colour(colours::BLUE),
+            ]
+        }
+        Demand::SetNumberToRandom { name, min, max } => {
+            vec![
+                "Set ".plain(),
+                name.in_colour(colours::RED),
+                " to a random number between ".plain(),
+                format!("{}-{}", min, max).in_colour(colours::BLUE),
+            ]
+        }
         Demand::SetVariableFromText { name } => {
             vec!["Set ".plain(), name.in_colour(colours::RED)]
         }
-        Demand::SetTextFromVariable { name } => {
+        Demand::SetTextFromVariable { name } | Demand::SetTextFromNumber { name } => {
             vec!["Set text from ".plain(), name.in_colour(colours::RED)]
         }
         Demand::SetTextFromPosition { axis, scale: _ } => {
@@ -1253,15 +1723,33 @@ pub fn fancy_demand_text(demand: &Demand) -> Vec<FancyText> {
         Demand::MoveChoreDown => simple_text("Move the chore down"),
         Demand::MoveQuestionUp => simple_text("Move the question up"),
         Demand::MoveQuestionDown => simple_text("Move the question down"),
+        Demand::NegateQuestion => simple_text("Negate the question"),
+        Demand::GroupQuestionWithNext(Group::Any) => {
+            simple_text("Group the question with the next one using or")
+        }
+        Demand::GroupQuestionWithNext(Group::All) => {
+            simple_text("Group the question with the next one using and")
+        }
+        Demand::UngroupQuestion => simple_text("Ungroup the question"),
         Demand::MoveDemandUp => simple_text("Move the demand up"),
         Demand::MoveDemandDown => simple_text("Move the demand down"),
         Demand::UpdateQuestion => simple_text("Update the question"),
         Demand::UpdateDemand => simple_text("Update the demand"),
         Demand::SetStartSprite => simple_text("Set the starting sprite"),
+        Demand::SetChoreLabel => simple_text("Set the chore's label"),
+        Demand::SetStartState => simple_text("Set the starting state"),
+        Demand::SetChoreState => simple_text("Set the chore's state"),
+        Demand::ToggleSolid => simple_text("Toggle whether the member is solid"),
         Demand::Quit => simple_text("Quit the game"),
         Demand::Stop => simple_text("Stop the game"),
         Demand::Play => simple_text("Play the game"),
         Demand::Pause => simple_text("Pause the game"),
+        Demand::PlayAtDifficulty(level) => {
+            vec![
+                "Play the game at ".plain(),
+                format!("{:?}", level).in_colour(colours::GREEN),
+            ]
+        }
         Demand::MoveToGame { name } => {
             vec![
                 "Switch to ".plain(),
@@ -0,0 +1,146 @@
+// Steps a cartridge frame by frame with no window or audio, for automated playthroughs
+//
+// usage: headless <cartridge.json> [--input <input.json>] [--seed <n>] [--frames <n>]
+//
+// The input file is a list of mouse and key states, each one held from its frame until the next:
+// [{ "frame": 30, "position": { "x": 128, "y": 72 }, "left": true, "keys": ["Right"] }]
+
+use green::{
+    doodle::DrawTool,
+    edit::Editor,
+    err::WhyResult,
+    inp::{Key, Mouse, RepeatableButton, RECORDED_KEYS},
+    meta::Environment,
+    music::MusicMaker,
+    nav::{Link, Navigation},
+    pixels,
+    play::{headless_game_from_cartridge, update_game, DifficultyLevel, SoundQueue},
+    seeded_rng::SeededRng,
+    serial::Cartridge,
+};
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+
+const DEFAULT_SEED: u64 = 0;
+const DEFAULT_INFINITE_FRAMES: usize = 600;
+
+#[derive(Debug, Deserialize)]
+struct ScriptedInput {
+    frame: usize,
+    position: pixels::Position,
+    #[serde(default)]
+    left: bool,
+    #[serde(default)]
+    middle: bool,
+    #[serde(default)]
+    right: bool,
+    #[serde(default)]
+    keys: Vec<Key>,
+}
+
+fn load_script(filename: &str) -> WhyResult<Vec<ScriptedInput>> {
+    let mut script: Vec<ScriptedInput> = serde_json::from_str(&std::fs::read_to_string(filename)?)
+        .map_err(|e| format!("Error deserialising input: {:?}", e))?;
+    script.sort_by_key(|step| step.frame);
+    Ok(script)
+}
+
+fn main() -> WhyResult<()> {
+    let mut cartridge_filename = None;
+    let mut script_filename = None;
+    let mut seed = DEFAULT_SEED;
+    let mut frames = None;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("Missing value for {}", arg));
+        match arg.as_str() {
+            "--input" => script_filename = Some(value()?),
+            "--seed" => seed = value()?.parse()?,
+            "--frames" => frames = Some(value()?.parse()?),
+            _ => cartridge_filename = Some(arg),
+        }
+    }
+
+    let cartridge_filename = cartridge_filename.ok_or(
+        "usage: headless <cartridge.json> [--input <input.json>] [--seed <n>] [--frames <n>]",
+    )?;
+    let cartridge = Cartridge::from_file_contents(&std::fs::read_to_string(cartridge_filename)?)?;
+    let script = match &script_filename {
+        Some(filename) => load_script(filename)?,
+        None => Vec::new(),
+    };
+
+    let mut game = headless_game_from_cartridge(cartridge, SeededRng::new(seed))?;
+
+    let frames = frames
+        .or(game.length.last_frame())
+        .unwrap_or(DEFAULT_INFINITE_FRAMES);
+
+    let mut environment = {
+        let context: HashMap<String, String> = std::fs::read_to_string("system/context.json")
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default();
+
+        Environment {
+            score: 0,
+            difficulty_level: DifficultyLevel::default(),
+            playback_rate: 1.0,
+            context,
+            rng: SeededRng::new(seed),
+        }
+    };
+    let mut editor = Editor::default();
+    let mut navigation = Navigation::new(Link::default());
+    let mut draw_tool = DrawTool::headless();
+    let mut music_maker = MusicMaker::init();
+    let shortcuts = HashSet::new();
+
+    let mut mouse = Mouse::default();
+    let mut keyboard = HashMap::from(RECORDED_KEYS.map(|key| (key, RepeatableButton::default())));
+    let mut steps = script.iter().peekable();
+    let mut held = (pixels::Position::default(), false, false, false);
+    let mut held_keys: &[Key] = &[];
+
+    while game.frame_number < frames {
+        while let Some(step) = steps.next_if(|step| step.frame <= game.frame_number) {
+            held = (step.position, step.left, step.middle, step.right);
+            held_keys = &step.keys;
+        }
+        let (position, is_left_down, is_middle_down, is_right_down) = held;
+        mouse.update(position, is_left_down, is_middle_down, is_right_down);
+        for (key_code, button) in keyboard.iter_mut() {
+            button.update(held_keys.iter().any(|key| key.key_code() == *key_code));
+        }
+
+        let mut sounds_to_play = SoundQueue::Ready {
+            sounds: HashSet::new(),
+        };
+
+        update_game(
+            &mut game,
+            mouse,
+            &keyboard,
+            &mut sounds_to_play,
+            &mut editor,
+            &mut environment,
+            &mut navigation,
+            &mut draw_tool,
+            &mut music_maker,
+            None,
+            &shortcuts,
+        )?;
+    }
+
+    println!("Win Status: {:?}", game.win_status);
+    println!("Frames: {}", game.frame_number);
+    for member in &game.members {
+        println!(
+            "{}: {}, {}",
+            member.name, member.position.x, member.position.y
+        );
+    }
+
+    Ok(())
+}
@@ -1,9 +1,9 @@
 use super::art::Sprite;
+use super::maths::Vec2;
+use super::music::MakerNote;
 use super::play;
 use super::play::{ChoreId, DemandId, QuestionId};
 use super::serial::{Demand, Question};
-use super::MakerNote;
-use super::Vec2;
 use crate::colours::Colour;
 use crate::music::PointInMusic;
 use crate::pixels;
@@ -75,6 +75,16 @@ pub enum Event {
         from: Sprite,
         to: Sprite,
     },
+    SetStartState {
+        index: usize,
+        from: Option<String>,
+        to: Option<String>,
+    },
+    SetSolid {
+        index: usize,
+        from: bool,
+        to: bool,
+    },
     // Music Stuff
     AddNote {
         editing_position: PointInMusic,
@@ -148,6 +158,12 @@ impl fmt::Display for Event {
             Event::SetStartSprite { .. } => {
                 write!(f, "Set start sprite")
             }
+            Event::SetStartState { .. } => {
+                write!(f, "Set start state")
+            }
+            Event::SetSolid { .. } => {
+                write!(f, "Set solid")
+            }
             Event::AddNote { .. } => {
                 write!(f, "Add note")
             }
@@ -42,6 +42,17 @@ impl Button {
     pub fn is_left_up(self) -> bool {
         self == Button::Up
     }
+
+    // No state means any state
+    pub fn is_in_state(self, state: Option<Button>) -> bool {
+        match state {
+            Some(Button::Press) => self.is_pressed(),
+            Some(Button::Down) => self.is_down(),
+            Some(Button::Up) => self.is_up(),
+            Some(Button::Release) => self.is_released(),
+            None => true,
+        }
+    }
 }
 
 pub fn updated_button(button: Button, is_down: bool) -> Button {
@@ -93,7 +104,7 @@ impl RepeatableButton {
     }
 }
 
-#[derive(Debug, Default, Copy, Clone)]
+#[derive(Debug, Default, Copy, Clone, Serialize, Deserialize)]
 pub struct Mouse {
     pub position: pixels::Position,
     pub drag: pixels::Position,
@@ -120,6 +131,105 @@ impl Mouse {
     }
 }
 
+// Keys that games can ask about, stored in cartridges instead of KeyCode
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq, Hash, EnumString)]
+pub enum Key {
+    #[default]
+    Space,
+    Enter,
+    Up,
+    Down,
+    Left,
+    Right,
+    W,
+    A,
+    S,
+    D,
+    Z,
+    X,
+}
+
+impl Key {
+    pub fn key_code(self) -> KeyCode {
+        match self {
+            Key::Space => KeyCode::Space,
+            Key::Enter => KeyCode::Enter,
+            Key::Up => KeyCode::Up,
+            Key::Down => KeyCode::Down,
+            Key::Left => KeyCode::Left,
+            Key::Right => KeyCode::Right,
+            Key::W => KeyCode::W,
+            Key::A => KeyCode::A,
+            Key::S => KeyCode::S,
+            Key::D => KeyCode::D,
+            Key::Z => KeyCode::Z,
+            Key::X => KeyCode::X,
+        }
+    }
+}
+
+// Directions and actions that games can ask about without caring which key is used
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq, Hash, EnumString)]
+pub enum Control {
+    #[default]
+    Up,
+    Down,
+    Left,
+    Right,
+    Action,
+}
+
+impl Control {
+    pub fn keys(self) -> &'static [Key] {
+        match self {
+            Control::Up => &[Key::Up, Key::W],
+            Control::Down => &[Key::Down, Key::S],
+            Control::Left => &[Key::Left, Key::A],
+            Control::Right => &[Key::Right, Key::D],
+            Control::Action => &[Key::Space, Key::Enter, Key::Z],
+        }
+    }
+}
+
+pub fn key_button(keyboard: &HashMap<KeyCode, RepeatableButton>, key: Key) -> Button {
+    keyboard
+        .get(&key.key_code())
+        .map(|button| button.button)
+        .unwrap_or_default()
+}
+
+// Held while any of its keys are held
+pub fn control_button(keyboard: &HashMap<KeyCode, RepeatableButton>, control: Control) -> Button {
+    control
+        .keys()
+        .iter()
+        .map(|key| key_button(keyboard, *key))
+        .fold(Button::Up, |combined, button| match (combined, button) {
+            (Button::Down, _) | (_, Button::Down) => Button::Down,
+            (Button::Press, _) | (_, Button::Press) => Button::Press,
+            (Button::Release, _) | (_, Button::Release) => Button::Release,
+            _ => Button::Up,
+        })
+}
+
+// Changing these means bumping REPLAY_VERSION
+pub const RECORDED_KEYS: [KeyCode; 14] = [
+    KeyCode::Z,
+    KeyCode::Y,
+    KeyCode::Backspace,
+    KeyCode::Space,
+    KeyCode::Enter,
+    KeyCode::Up,
+    KeyCode::Down,
+    KeyCode::Left,
+    KeyCode::Right,
+    KeyCode::W,
+    KeyCode::A,
+    KeyCode::S,
+    KeyCode::D,
+    KeyCode::X,
+];
+
 #[derive(Debug, Clone)]
 pub struct Input {
     pub outer: Mouse,
@@ -138,20 +248,9 @@ impl Input {
         temp_save: &mut bool,
     ) {
         self.chars_pressed = pressed_chars();
-        self.keyboard
-            .get_mut(&KeyCode::Z)
-            .unwrap()
-            .update(macroquad::input::is_key_down(KeyCode::Z));
-
-        self.keyboard
-            .get_mut(&KeyCode::Y)
-            .unwrap()
-            .update(macroquad::input::is_key_down(KeyCode::Y));
-
-        self.keyboard
-            .get_mut(&KeyCode::Backspace)
-            .unwrap()
-            .update(macroquad::input::is_key_down(KeyCode::Backspace));
+        for (key, button) in self.keyboard.iter_mut() {
+            button.update(macroquad::input::is_key_down(*key));
+        }
 
         let mouse_position = {
             let (x, y) = macroquad::input::mouse_position();
@@ -0,0 +1,160 @@
+#![allow(clippy::comparison_chain)]
+#![allow(clippy::approx_constant)]
+
+pub const TEMP_TESTING_INTRO_TEXT: bool = false;
+
+use files::FileSystem;
+use macroquad::{color::Color as Colour, logging as log, texture::FilterMode};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    aud::AudioPlayer,
+    err::WhyResult,
+    meta::INTRO_FONT_FILENAME,
+    nav::Link,
+    play::{cartridge_from_game, game_from_cartridge},
+    rend::{Image, Texture},
+};
+
+pub mod anim;
+pub mod art;
+pub mod aud;
+pub mod coll;
+pub mod colours;
+pub mod common;
+pub mod doodle;
+pub mod drawer;
+pub mod edit;
+pub mod err;
+pub mod files;
+pub mod history;
+pub mod inp;
+pub mod lint;
+pub mod maths;
+pub mod menu;
+pub mod meta;
+pub mod migrate;
+pub mod music;
+pub mod nav;
+pub mod pixels;
+pub mod play;
+pub mod rend;
+pub mod replay;
+pub mod seeded_rng;
+pub mod serial;
+pub mod sys;
+pub mod time;
+pub mod track;
+pub mod whydraw;
+pub mod window;
+
+impl coll::Grid for Image {
+    fn size(&self) -> (i32, i32) {
+        (self.width as i32, self.height as i32)
+    }
+
+    fn get_square_bit(&self, position: pixels::Position) -> bool {
+        self.get_pixel(position.x as u32, position.y as u32).a != 0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::doodle::PAINT_SIZE;
+    use crate::pixels::Position;
+    use crate::pixels::Rect as SecRect;
+
+    use super::coll::{Grid, _is_subsection_square_active};
+    use super::*;
+
+    #[test]
+    fn test_grid() {
+        #[rustfmt::skip]
+        let grid = Image::gen_image_color(PAINT_SIZE, PAINT_SIZE, colours::BLANK);
+
+        assert!(!grid.is_square_active(Position::new(0, 0)));
+    }
+
+    #[test]
+    fn test_outside_grid() {
+        #[rustfmt::skip]
+        let grid = Image::gen_image_color(PAINT_SIZE, PAINT_SIZE, colours::BLANK);
+
+        assert!(!grid.is_square_active(Position::new(-5, -5)));
+    }
+
+    #[test]
+    fn test_subsection() {
+        #[rustfmt::skip]
+        let mut grid = Image::gen_image_color(PAINT_SIZE, PAINT_SIZE, colours::BLANK);
+        grid.set_pixel(1, 1, colours::WHITE);
+
+        let section = SecRect::aabb(1, 1, 3, 3);
+        assert!(_is_subsection_square_active(
+            &grid,
+            Position::new(0, 0),
+            section
+        ));
+        assert!(!_is_subsection_square_active(
+            &grid,
+            Position::new(1, 0),
+            section
+        ));
+    }
+}
+
+//pub async fn temp_load(collection: &str, name: &str) -> WhyResult<play::Game> {
+//    log::debug!("game name: {}/{}", collection, name);
+//    let filename = format!("collections/{}/{}.json", collection, name);
+//    let save_contents = macroquad::file::load_string(&filename).await?;
+//    let cartridge: Result<Cartridge, _> = serde_json::from_str(&save_contents);
+//
+//    let rng = SeededRng::new(macroquad::miniquad::date::now() as _);
+//
+//    cartridge
+//        .map(|cart| game_from_cartridge(cart, rng))
+//        .map_err(|e| format!("Error deserialising game: {:?}", e).into())
+//}
+
+pub fn temp_save(collection: &str, name: &str, game: play::Game) -> WhyResult<()> {
+    log::debug!("write game name: {}/{}", collection, name);
+    let filename = format!("collections/{}/{}.json", collection, name);
+    let cartridge = cartridge_from_game(game);
+    let s = serde_json::to_string_pretty(&cartridge).unwrap();
+    std::fs::write(filename, s)?;
+    Ok(())
+}
+
+async fn system_texture(filename: &str) -> WhyResult<Texture> {
+    texture_from_dir("system", filename).await
+}
+
+async fn texture_from_dir(dir: &str, filename: &str) -> WhyResult<Texture> {
+    let texture = macroquad::texture::load_texture(&format!("{}/{}", dir, filename)).await?;
+    texture.set_filter(FilterMode::Nearest);
+    Ok(texture)
+}
+
+fn texture_from_bytes(bytes: &[u8]) -> WhyResult<Texture> {
+    let texture = Texture::from_file_with_format(bytes, None);
+    texture.set_filter(FilterMode::Nearest);
+    Ok(texture)
+}
+
+async fn images_texture(filename: &str) -> WhyResult<Texture> {
+    texture_from_dir("images", filename).await
+}
+
+#[cfg(target_arch = "wasm32")]
+use sapp_jsutils::JsObject;
+
+#[cfg(target_arch = "wasm32")]
+extern "C" {
+    fn hi_from_wasm(x: JsObject);
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct BootInfo {
+    pub initial_game: Link,
+    pub initial_subgame: Link,
+}
@@ -0,0 +1,294 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use base64::{engine::general_purpose::STANDARD_NO_PAD as BaseEncoder, Engine};
+
+use crate::art::{Sprite, SpriteSize};
+use crate::aud::SOUND_LIBRARY;
+use crate::drawer::sheet_source_rect;
+use crate::pixels;
+use crate::serial::{
+    Cartridge, Chore, CollisionWith, Demand, JumpLocation, Motion, ParticleStyle, Question, When,
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Problem {
+    MissingMember { name: String },
+    MissingTag { tag: String },
+    MissingSound { name: String },
+    SpriteOutsideSheet { sprite: Sprite },
+    RandomRangeBackwards { start: usize, end: usize },
+    UnreadableImage,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diagnostic {
+    pub member: Option<String>,
+    pub chore: Option<usize>,
+    pub problem: Problem,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(member) = &self.member {
+            write!(f, "{}", member)?;
+            if let Some(chore) = self.chore {
+                write!(f, " chore {}", chore + 1)?;
+            }
+            write!(f, ": ")?;
+        }
+        match &self.problem {
+            Problem::MissingMember { name } => write!(f, "no member called '{}'", name),
+            Problem::MissingTag { tag } => write!(f, "no member tagged '{}'", tag),
+            Problem::MissingSound { name } => write!(f, "no sound called '{}'", name),
+            Problem::SpriteOutsideSheet { sprite } => write!(
+                f,
+                "sprite {} ({}) is outside the sprite sheet",
+                sprite.index, sprite.size
+            ),
+            Problem::RandomRangeBackwards { start, end } => {
+                write!(
+                    f,
+                    "random time starts at {} after it ends at {}",
+                    start, end
+                )
+            }
+            Problem::UnreadableImage => write!(f, "sprite sheet image could not be read"),
+        }
+    }
+}
+
+pub fn lint(cartridge: &Cartridge) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    let sheet_size = sheet_size(cartridge);
+    if sheet_size.is_none() {
+        diagnostics.push(Diagnostic {
+            member: None,
+            chore: None,
+            problem: Problem::UnreadableImage,
+        });
+    }
+
+    let member_names: HashSet<&str> = cartridge
+        .members
+        .iter()
+        .map(|member| member.name.as_str())
+        .collect();
+
+    for member in &cartridge.members {
+        let mut problems = Vec::new();
+        check_sprite(member.sprite, sheet_size, &mut problems);
+        diagnostics.extend(problems.into_iter().map(|problem| Diagnostic {
+            member: Some(member.name.clone()),
+            chore: None,
+            problem,
+        }));
+
+        for (index, chore) in member.todo_list.iter().enumerate() {
+            let problems = chore_problems(chore, cartridge, &member_names, sheet_size);
+            diagnostics.extend(problems.into_iter().map(|problem| Diagnostic {
+                member: Some(member.name.clone()),
+                chore: Some(index),
+                problem,
+            }));
+        }
+    }
+
+    diagnostics
+}
+
+fn chore_problems(
+    chore: &Chore,
+    cartridge: &Cartridge,
+    member_names: &HashSet<&str>,
+    sheet_size: Option<pixels::Size>,
+) -> Vec<Problem> {
+    let mut problems = Vec::new();
+    let check_name = |name: &str, problems: &mut Vec<Problem>| {
+        if !member_names.contains(name) {
+            problems.push(Problem::MissingMember {
+                name: name.to_owned(),
+            });
+        }
+    };
+    let check_tag = |tag: &str, problems: &mut Vec<Problem>| {
+        if !cartridge
+            .members
+            .iter()
+            .any(|member| member.tags.iter().any(|t| t == tag))
+        {
+            problems.push(Problem::MissingTag {
+                tag: tag.to_owned(),
+            });
+        }
+    };
+
+    for question in chore.questions.iter().flat_map(Question::leaves) {
+        match question {
+            Question::IsTimeAt(When::Random { start, end }) if start > end => {
+                problems.push(Problem::RandomRangeBackwards {
+                    start: *start,
+                    end: *end,
+                });
+            }
+            Question::IsSwitchSetTo { name, .. }
+            | Question::IsCollidingWith(CollisionWith::Member { name }) => {
+                check_name(name, &mut problems);
+            }
+            Question::IsTaggedSwitchSetTo { tag, .. }
+            | Question::IsCollidingWith(CollisionWith::Tagged { tag, .. }) => {
+                check_tag(tag, &mut problems);
+            }
+            Question::IsSpriteSetTo(sprite) => {
+                check_sprite(*sprite, sheet_size, &mut problems);
+            }
+            _ => {}
+        }
+    }
+
+    for demand in &chore.demands {
+        if let Demand::Spawn { template, .. } = demand {
+            check_name(template, &mut problems);
+        }
+        match demand {
+            Demand::Motion(
+                Motion::Swap { name }
+                | Motion::Target { name, .. }
+                | Motion::AttachFromPositions { name }
+                | Motion::JumpTo(JumpLocation::Member { name }),
+            )
+            | Demand::Spawn {
+                at: JumpLocation::Member { name },
+                ..
+            }
+            | Demand::CameraFollow { name, .. }
+            | Demand::Send { to: Some(name), .. } => {
+                check_name(name, &mut problems);
+            }
+            Demand::SetTaggedSwitch { tag, .. }
+            | Demand::Motion(
+                Motion::TargetTagged { tag, .. } | Motion::JumpTo(JumpLocation::Tagged { tag }),
+            )
+            | Demand::Spawn {
+                at: JumpLocation::Tagged { tag },
+                ..
+            } => {
+                check_tag(tag, &mut problems);
+            }
+            // Sounds can come from the cartridge or from the shared library
+            Demand::PlaySound { name }
+                if !cartridge.sounds.contains_key(name)
+                    && !SOUND_LIBRARY.contains(&name.as_str()) =>
+            {
+                problems.push(Problem::MissingSound {
+                    name: name.to_owned(),
+                });
+            }
+            Demand::SetSprite(sprite)
+            | Demand::EmitParticles {
+                style: ParticleStyle::Sprite(sprite),
+                ..
+            } => {
+                check_sprite(*sprite, sheet_size, &mut problems);
+            }
+            Demand::Animate { sprites, .. } => {
+                for sprite in sprites {
+                    check_sprite(*sprite, sheet_size, &mut problems);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    problems
+}
+
+fn sheet_size(cartridge: &Cartridge) -> Option<pixels::Size> {
+    let bytes = BaseEncoder.decode(&cartridge.image.0).ok()?;
+    let (w, h) = image::io::Reader::new(std::io::Cursor::new(bytes))
+        .with_guessed_format()
+        .ok()?
+        .into_dimensions()
+        .ok()?;
+    Some(pixels::Size::new(w, h))
+}
+
+fn check_sprite(sprite: Sprite, sheet_size: Option<pixels::Size>, problems: &mut Vec<Problem>) {
+    let Some(sheet_size) = sheet_size else {
+        return;
+    };
+    if sprite.size == SpriteSize::Empty {
+        return;
+    }
+
+    let rect = sheet_source_rect(sprite);
+    if rect.min.x < 0
+        || rect.min.y < 0
+        || rect.max.x > sheet_size.w as i32
+        || rect.max.y > sheet_size.h as i32
+    {
+        problems.push(Problem::SpriteOutsideSheet { sprite });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::serial::Member;
+
+    #[test]
+    fn test_lint_missing_references() {
+        let cartridge = Cartridge {
+            members: vec![Member {
+                name: "Frog".to_owned(),
+                todo_list: vec![Chore {
+                    label: None,
+                    state: None,
+                    questions: vec![Question::IsTimeAt(When::Random { start: 10, end: 5 })],
+                    demands: vec![
+                        Demand::Motion(Motion::Target {
+                            name: "Fly".to_owned(),
+                            offset: pixels::Position::default(),
+                            speed: Default::default(),
+                        }),
+                        Demand::Spawn {
+                            template: "Egg".to_owned(),
+                            at: JumpLocation::Mouse,
+                        },
+                        Demand::PlaySound {
+                            name: "Croak".to_owned(),
+                        },
+                        Demand::PlaySound {
+                            name: "Coin".to_owned(),
+                        },
+                    ],
+                }],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let problems: Vec<Problem> = lint(&cartridge)
+            .into_iter()
+            .map(|diagnostic| diagnostic.problem)
+            .collect();
+
+        assert_eq!(
+            problems,
+            vec![
+                Problem::UnreadableImage,
+                Problem::RandomRangeBackwards { start: 10, end: 5 },
+                Problem::MissingMember {
+                    name: "Fly".to_owned()
+                },
+                Problem::MissingMember {
+                    name: "Egg".to_owned()
+                },
+                Problem::MissingSound {
+                    name: "Croak".to_owned()
+                },
+            ]
+        );
+    }
+}
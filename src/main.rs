@@ -1,8 +1,3 @@
-#![allow(clippy::comparison_chain)]
-#![allow(clippy::approx_constant)]
-
-const TEMP_TESTING_INTRO_TEXT: bool = false;
-
 // TODO: EACH NEW GAME IS LIKE A COMPLETE CARTRIDGE RESET
 // TODO: Download mq js bundle and store it locally
 // TODO: ResetQueue when doing this stuff?
@@ -29,71 +24,40 @@ const TEMP_TESTING_INTRO_TEXT: bool = false;
 // TODO: Will that NextInQueue -> BackToStart work in all cases? ResetToThisPoint action...?
 // TODO: Bug trying to SetText demand in Maker for Screen and Screen2
 
-use art::SpriteSize;
-use edit::sprite_from_context;
-use files::FileSystem;
-use macroquad::{
-    color::{colors as quad_colours, Color as Colour},
-    experimental::coroutines::{start_coroutine, Coroutine},
-    input::KeyCode,
-    logging as log,
-    miniquad::conf::Icon,
-    texture::FilterMode,
-    window::{next_frame, Conf},
-};
-use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, ffi::OsStr, sync::mpsc};
-
-use crate::{
+use green::{
+    art::SpriteSize,
     aud::{default_smf, AudioParameters, AudioPlayer, MidiFileContainer},
     doodle::DrawTool,
     drawer::Camera,
-    edit::{AssetChoices, Editor, ImageChoice},
+    edit::{sprite_from_context, AssetChoices, Editor, ImageChoice},
     err::WhyResult,
-    inp::{pressed_chars, Input, Mouse, RepeatableButton},
+    files::FileSystem,
+    inp::{pressed_chars, Input, Mouse, RepeatableButton, RECORDED_KEYS},
     maths::Vec2,
     meta::{
-        update_metagame, Environment, MenuOutcome, Transition, INITIAL_SCALE, INTRO_FONT_FILENAME,
-        OUTER_CENTRE, OUTER_HEIGHT, OUTER_WIDTH, PLAY_SCREEN_NAME,
+        update_metagame, Environment, MenuOutcome, Transition, INITIAL_SCALE, OUTER_CENTRE,
+        OUTER_HEIGHT, OUTER_WIDTH, PLAY_SCREEN_NAME,
     },
-    music::{MakerNote, MusicMaker},
-    nav::{Link, Navigation},
-    play::{cartridge_from_game, game_from_cartridge, position_in_world, DifficultyLevel},
-    rend::{Image, Texture},
+    music::MusicMaker,
+    nav::Navigation,
+    pixels, play,
+    play::{position_in_world, DifficultyLevel},
+    rend::Image,
+    replay::{Replay, Session},
     seeded_rng::SeededRng,
-    serial::Cartridge,
     time::TimeKeeping,
     whydraw::WhyDrawer,
+    window, BootInfo,
 };
-
-mod anim;
-mod art;
-mod aud;
-mod coll;
-mod colours;
-mod common;
-mod doodle;
-mod drawer;
-mod edit;
-mod err;
-mod files;
-mod history;
-mod inp;
-mod maths;
-mod menu;
-mod meta;
-mod music;
-mod nav;
-mod pixels;
-mod play;
-mod rend;
-mod seeded_rng;
-mod serial;
-mod sys;
-mod time;
-mod track;
-mod whydraw;
-mod window;
+use macroquad::{
+    color::colors as quad_colours,
+    experimental::coroutines::{start_coroutine, Coroutine},
+    input::KeyCode,
+    logging as log,
+    miniquad::conf::Icon,
+    window::{next_frame, Conf},
+};
+use std::{collections::HashMap, ffi::OsStr, sync::mpsc};
 
 const INITIAL_FULLSCREEN: bool = false;
 const INITIAL_WINDOW_WIDTH: u32 = (OUTER_WIDTH as f32 * INITIAL_SCALE) as u32;
@@ -105,103 +69,6 @@ const IS_RECORDING_FPS: bool = false;
 
 //const DEFAULT_FONT_FILENAME: &'static str = "analog.png";
 
-impl coll::Grid for Image {
-    fn size(&self) -> (i32, i32) {
-        (self.width as i32, self.height as i32)
-    }
-
-    fn get_square_bit(&self, position: pixels::Position) -> bool {
-        self.get_pixel(position.x as u32, position.y as u32).a != 0.0
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::doodle::PAINT_SIZE;
-    use crate::pixels::Position;
-    use crate::pixels::Rect as SecRect;
-
-    use super::coll::{Grid, _is_subsection_square_active};
-    use super::*;
-
-    #[test]
-    fn test_grid() {
-        #[rustfmt::skip]
-        let grid = Image::gen_image_color(PAINT_SIZE, PAINT_SIZE, colours::BLANK);
-
-        assert!(!grid.is_square_active(Position::new(0, 0)));
-    }
-
-    #[test]
-    fn test_outside_grid() {
-        #[rustfmt::skip]
-        let grid = Image::gen_image_color(PAINT_SIZE, PAINT_SIZE, colours::BLANK);
-
-        assert!(!grid.is_square_active(Position::new(-5, -5)));
-    }
-
-    #[test]
-    fn test_subsection() {
-        #[rustfmt::skip]
-        let mut grid = Image::gen_image_color(PAINT_SIZE, PAINT_SIZE, colours::BLANK);
-        grid.set_pixel(1, 1, colours::WHITE);
-
-        let section = SecRect::aabb(1, 1, 3, 3);
-        assert!(_is_subsection_square_active(
-            &grid,
-            Position::new(0, 0),
-            section
-        ));
-        assert!(!_is_subsection_square_active(
-            &grid,
-            Position::new(1, 0),
-            section
-        ));
-    }
-}
-
-//pub async fn temp_load(collection: &str, name: &str) -> WhyResult<play::Game> {
-//    log::debug!("game name: {}/{}", collection, name);
-//    let filename = format!("collections/{}/{}.json", collection, name);
-//    let save_contents = macroquad::file::load_string(&filename).await?;
-//    let cartridge: Result<Cartridge, _> = serde_json::from_str(&save_contents);
-//
-//    let rng = SeededRng::new(macroquad::miniquad::date::now() as _);
-//
-//    cartridge
-//        .map(|cart| game_from_cartridge(cart, rng))
-//        .map_err(|e| format!("Error deserialising game: {:?}", e).into())
-//}
-
-pub fn temp_save(collection: &str, name: &str, game: play::Game) -> WhyResult<()> {
-    log::debug!("write game name: {}/{}", collection, name);
-    let filename = format!("collections/{}/{}.json", collection, name);
-    let cartridge = cartridge_from_game(game);
-    let s = serde_json::to_string_pretty(&cartridge).unwrap();
-    std::fs::write(filename, s)?;
-    Ok(())
-}
-
-async fn system_texture(filename: &str) -> WhyResult<Texture> {
-    texture_from_dir("system", filename).await
-}
-
-async fn texture_from_dir(dir: &str, filename: &str) -> WhyResult<Texture> {
-    let texture = macroquad::texture::load_texture(&format!("{}/{}", dir, filename)).await?;
-    texture.set_filter(FilterMode::Nearest);
-    Ok(texture)
-}
-
-fn texture_from_bytes(bytes: &[u8]) -> WhyResult<Texture> {
-    let texture = Texture::from_file_with_format(bytes, None);
-    texture.set_filter(FilterMode::Nearest);
-    Ok(texture)
-}
-
-async fn images_texture(filename: &str) -> WhyResult<Texture> {
-    texture_from_dir("images", filename).await
-}
-
 fn load_icon_to_array(bytes: &'static [u8], out: &mut [u8]) {
     let image = Image::from_file_with_format(bytes, None).unwrap();
     let mut i = 0;
@@ -238,22 +105,34 @@ fn window_conf() -> Conf {
     }
 }
 
-#[cfg(target_arch = "wasm32")]
-use sapp_jsutils::JsObject;
-
-#[cfg(target_arch = "wasm32")]
-extern "C" {
-    fn hi_from_wasm(x: JsObject);
-}
-
-fn rng_from_time() -> SeededRng {
-    SeededRng::new(macroquad::miniquad::date::now() as u64)
+fn seed_from_time() -> u64 {
+    macroquad::miniquad::date::now() as u64
 }
 
-#[derive(Serialize, Deserialize)]
-struct BootInfo {
-    initial_game: Link,
-    initial_subgame: Link,
+// --record <file> saves this session's input, --replay <file> plays a saved one back
+async fn session_from_args() -> WhyResult<Session> {
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--record" => {
+                let filename = args.next().ok_or("Missing filename for --record")?;
+                return Ok(Session::Recording {
+                    replay: Replay::new(seed_from_time()),
+                    filename,
+                });
+            }
+            "--replay" => {
+                let filename = args.next().ok_or("Missing filename for --replay")?;
+                let s = macroquad::file::load_string(&filename).await?;
+                return Ok(Session::Replaying {
+                    replay: Replay::from_file_contents(&s)?,
+                    frame: 0,
+                });
+            }
+            _ => log::warn!("Unknown argument: {}", arg),
+        }
+    }
+    Ok(Session::Live)
 }
 
 #[macroquad::main(window_conf)]
@@ -266,6 +145,12 @@ async fn main() -> WhyResult<()> {
 
     let mut transition = Transition::None;
 
+    let mut session = session_from_args().await?;
+    let seed = match &session {
+        Session::Live => seed_from_time(),
+        Session::Recording { replay, .. } | Session::Replaying { replay, .. } => replay.seed,
+    };
+
     let mut environment = {
         let context: HashMap<String, String> = {
             let s = macroquad::file::load_string("system/context.json").await?;
@@ -277,7 +162,7 @@ async fn main() -> WhyResult<()> {
             difficulty_level: DifficultyLevel::default(),
             playback_rate: 1.0,
             context,
-            rng: rng_from_time(),
+            rng: SeededRng::new(seed),
         }
     };
 
@@ -357,8 +242,18 @@ async fn main() -> WhyResult<()> {
 
     let file_system = FileSystem { memfs };
 
-    let mut game = play::Game::load(&boot_info.initial_game, &file_system).await?;
-    let mut subgame = play::Game::load(&boot_info.initial_subgame, &file_system).await?;
+    let mut game = play::Game::load(
+        &boot_info.initial_game,
+        &file_system,
+        environment.rng.next_rng(),
+    )
+    .await?;
+    let mut subgame = play::Game::load(
+        &boot_info.initial_subgame,
+        &file_system,
+        environment.rng.next_rng(),
+    )
+    .await?;
 
     environment.init_vars(&subgame, &boot_info);
 
@@ -381,9 +276,11 @@ async fn main() -> WhyResult<()> {
     let mut inner_camera = match subgame.size {
         play::Size::Small => Camera::Inner {
             position: game.screen_position(),
+            scroll: pixels::Position::default(),
         },
         play::Size::Big => Camera::EditedOuter {
             position: game.screen_position(),
+            scroll: pixels::Position::default(),
         },
     };
     let mut input = {
@@ -400,11 +297,7 @@ async fn main() -> WhyResult<()> {
             position: position_in_world(original_mouse_position, inner_camera),
             ..Default::default()
         };
-        let keyboard = HashMap::from([
-            (KeyCode::Z, RepeatableButton::default()),
-            (KeyCode::Y, RepeatableButton::default()),
-            (KeyCode::Backspace, RepeatableButton::default()),
-        ]);
+        let keyboard = HashMap::from(RECORDED_KEYS.map(|key| (key, RepeatableButton::default())));
         Input {
             outer: outer_mouse,
             inner: inner_mouse,
@@ -513,21 +406,29 @@ async fn main() -> WhyResult<()> {
 
         time_keeping.update(macroquad::time::get_time(), environment.playback_rate);
 
+        // Only scrolls while playing, editing always shows the top left of the world
+        let is_playing = game
+            .members
+            .iter()
+            .any(|m| m.text.contents == PLAY_SCREEN_NAME);
+        let scroll = if is_playing {
+            subgame.scroll()
+        } else {
+            pixels::Position::default()
+        };
         inner_camera = match subgame.size {
             play::Size::Small => Camera::Inner {
                 position: game.screen_position(),
+                scroll,
             },
             // TODO: ?
             play::Size::Big => {
-                if game
-                    .members
-                    .iter()
-                    .any(|m| m.text.contents == PLAY_SCREEN_NAME)
-                {
-                    Camera::Outer
+                if is_playing {
+                    Camera::ScrolledOuter { scroll }
                 } else {
                     Camera::EditedOuter {
                         position: OUTER_CENTRE,
+                        scroll,
                     }
                 }
             }
@@ -545,11 +446,13 @@ async fn main() -> WhyResult<()> {
                     SpriteSize::OuterBg => {
                         inner_camera = Camera::EditedOuter {
                             position: game.screen_position(),
+                            scroll: pixels::Position::default(),
                         }
                     }
                     _ => {
                         inner_camera = Camera::Inner {
                             position: game.screen_position(),
+                            scroll: pixels::Position::default(),
                         }
                     }
                 }
@@ -557,10 +460,11 @@ async fn main() -> WhyResult<()> {
         }
 
         // TODO: Unoptimised, maybe don't worry
-        environment.update_var(
-            "Selected Member",
-            subgame.members[editor.selected_index].name.to_owned(),
-        );
+        // Every member might have been destroyed while playing
+        if let Some(member) = subgame.members.get(editor.selected_index) {
+            environment.update_var("Selected Member", member.name.to_owned());
+            environment.update_var("Solid", if member.solid { "Solid" } else { "Not Solid" });
+        }
 
         // TODO: Have this happen in input.update() but still be smooth
         input.mouse_scroll = macroquad::input::mouse_wheel().1;
@@ -572,8 +476,13 @@ async fn main() -> WhyResult<()> {
                 game.screen_position_option(),
                 &mut draw_tool.tracker.temp_save,
             );
+            session.handle_input(&mut input);
+            if session.is_replay_finished() {
+                log::info!("Replay finished");
+                session = Session::Live;
+            }
 
-            let outcome = update_metagame(
+            let result = update_metagame(
                 &mut environment,
                 &mut navigation,
                 (&mut editor, &mut dummy_editor),
@@ -587,7 +496,12 @@ async fn main() -> WhyResult<()> {
                 time_keeping,
                 &file_system,
             )
-            .await?;
+            .await;
+            let outcome = result.unwrap_or_else(|e| {
+                log::error!("{}", e);
+                game.error = Some(e.to_string());
+                MenuOutcome::None
+            });
 
             // TODO: Removed important if stmt, or...
             //if game.music_maker_member().is_some() {
@@ -633,7 +547,13 @@ async fn main() -> WhyResult<()> {
 
         if let Some(link) = navigation.next_game.take() {
             time_keeping.reset();
-            game = play::Game::load(&link, &file_system).await?;
+            match play::Game::load(&link, &file_system, environment.rng.next_rng()).await {
+                Ok(loaded) => game = loaded,
+                Err(e) => {
+                    log::error!("Could not load {}: {}", link.game, e);
+                    game.error = Some(format!("Could not load {}: {}", link.game, e));
+                }
+            }
             game.frame_number = 0;
             log::debug!("FRAME NUMBER: {}", game.frame_number);
             // TODO: Think about if this is what we want all the time
@@ -695,5 +615,7 @@ async fn main() -> WhyResult<()> {
 
     log::debug!("Quitting");
 
+    session.finish()?;
+
     Ok(())
 }
@@ -17,7 +17,7 @@ use crate::nav::{Link, Navigation};
 use crate::pixels;
 use crate::play::{self, rename_in_todo_list, update_game, Assets, DifficultyLevel, SoundQueue};
 use crate::seeded_rng::SeededRng;
-use crate::serial::{self, ImageString, Shortcut, SoundString};
+use crate::serial::{self, ImageString, Shortcut, SoundString, WorldSize};
 use crate::time::TimeKeeping;
 use crate::AudioPlayer;
 
@@ -46,6 +46,7 @@ pub const EDITABLE_SCREEN_NAME: &str = "{Screen}";
 pub const SCREEN_NAME: &str = "{Screen}";
 pub const CHOOSE_AREA_NAME: &str = "{Choose Area}";
 pub const CHOOSE_POINT_NAME: &str = "{Choose Point}";
+pub const CHOOSE_PATH_NAME: &str = "{Choose Path}";
 pub const PLAY_SCREEN_NAME: &str = "{Play Screen}";
 pub const MUSIC_MAKER_NAME: &str = "{Music Maker}";
 
@@ -137,6 +138,19 @@ impl Environment {
         self.context.insert(key.into(), value.into());
     }
 
+    pub fn update_world_size_vars(&mut self, subgame: &play::Game) {
+        let world_size = subgame.world_size();
+        self.update_var("World Width", world_size.w.to_string());
+        self.update_var("World Height", world_size.h.to_string());
+    }
+
+    pub fn update_difficulty_scaling_var(&mut self, subgame: &play::Game) {
+        self.update_var(
+            "Scales With Difficulty",
+            subgame.scales_with_difficulty.to_string(),
+        );
+    }
+
     pub fn update_var_as_debug<K: Into<String>, V: std::fmt::Debug>(&mut self, key: K, value: V) {
         self.context.insert(key.into(), format!("{:?}", value));
     }
@@ -163,6 +177,8 @@ impl Environment {
         // TODO: Or initial_game?
         self.update_var("Collection", boot_info.initial_subgame.collection.clone());
         self.update_var_as_debug("Game Size", subgame.size);
+        self.update_world_size_vars(subgame);
+        self.update_difficulty_scaling_var(subgame);
         self.update_var_as_debug("Length", subgame.length);
 
         self.update_var_as_debug("Difficulty", self.difficulty_level);
@@ -211,9 +227,10 @@ pub async fn update_metagame(
         | Transition::FadeOut {
             game: older_game, ..
         } => {
-            update_game(
+            let result = update_game(
                 older_game,
                 input.outer,
+                &input.keyboard,
                 &mut sounds_to_play,
                 editor,
                 environment,
@@ -223,13 +240,15 @@ pub async fn update_metagame(
                 Some(subgame),
                 &shortcuts,
             );
+            older_game.halt_on_error(result);
         }
         Transition::None => {}
     }
 
-    let (mut new_events, mut menu_actions) = update_game(
+    let result = update_game(
         game,
         input.outer,
+        &input.keyboard,
         &mut sounds_to_play,
         editor,
         environment,
@@ -239,6 +258,16 @@ pub async fn update_metagame(
         Some(subgame),
         &shortcuts,
     );
+    let (mut new_events, mut menu_actions) = game.halt_on_error(result);
+
+    // Clicking the error overlay skips a broken game, or stops a broken subgame
+    if input.outer.left_button.is_pressed() {
+        if game.error.take().is_some() {
+            menu_actions.push(menu::Action::NextInQueue);
+        } else if subgame.error.take().is_some() {
+            menu_actions.push(menu::Action::Stop);
+        }
+    }
 
     // TODO: Rework using an question/demand and undoable event
     if environment.context["Game Size"] == "Small" {
@@ -247,6 +276,18 @@ pub async fn update_metagame(
     if environment.context["Game Size"] == "Big" {
         subgame.size = play::Size::Big;
     }
+    // Worlds that fit on the screen are saved without a world size
+    if let (Some(width), Some(height)) = (
+        environment.get_typed_var::<u32>("World Width"),
+        environment.get_typed_var::<u32>("World Height"),
+    ) {
+        let screen_size = subgame.size.screen_size();
+        subgame.world_size = (width > screen_size.w || height > screen_size.h)
+            .then_some(WorldSize { width, height });
+    }
+    if let Some(scales) = environment.get_typed_var::<bool>("Scales With Difficulty") {
+        subgame.scales_with_difficulty = scales;
+    }
     if environment.context["Length"] == "Short" {
         subgame.length = play::Length::Short;
     }
@@ -277,9 +318,11 @@ pub async fn update_metagame(
         if !has_playable_screen(&game.members) {
             menu_actions.push(menu::Action::Stop);
         } else {
-            update_game(
+            let selected_name = editor.selected_name(&subgame.members);
+            let result = update_game(
                 subgame,
                 input.inner,
+                &input.keyboard,
                 &mut sounds_to_play,
                 dummy_editor,
                 environment,
@@ -289,6 +332,8 @@ pub async fn update_metagame(
                 None,
                 &shortcuts,
             );
+            subgame.halt_on_error(result);
+            editor.reselect(&subgame.members, selected_name);
         }
     }
 
@@ -354,7 +399,11 @@ pub async fn update_metagame(
                 }
             }
         }
-    } else if has_editable_screen(&game.members) && editor.inner_copy.is_none() {
+    } else if has_editable_screen(&game.members)
+        && editor.inner_copy.is_none()
+        // A paused subgame might have had every member destroyed
+        && !subgame.members.is_empty()
+    {
         // TODO: ?
         #[cfg(target_arch = "wasm32")]
         if input.keyboard[&KeyCode::Backspace].is_repeated {
@@ -376,7 +425,10 @@ pub async fn update_metagame(
         }
     }
 
-    if has_editable_screen(&game.members) && editor.inner_copy.is_none() {
+    if has_editable_screen(&game.members)
+        && editor.inner_copy.is_none()
+        && !subgame.members.is_empty()
+    {
         let is_in_select_mode = environment.context["Editor Mode"] == "Select";
         if is_in_select_mode && input.outer.left_button.is_pressed() {
             let hovered_indices = hovered_in_general_area(
@@ -556,6 +608,27 @@ pub async fn update_metagame(
                 environment.update_var("Y", input.inner.position.y.to_string());
             }
         }
+        // Every press inside the screen adds another point
+        let position = input.inner.position;
+        let is_inside_screen = position.x >= 0
+            && position.y >= 0
+            && position.x < INNER_WIDTH as i32
+            && position.y < INNER_HEIGHT as i32;
+        if member.text.contents == CHOOSE_PATH_NAME
+            && is_inside_screen
+            && input.outer.left_button.is_pressed()
+        {
+            let mut points = edit::path_from_text(
+                environment
+                    .context
+                    .get("Path")
+                    .map(String::as_str)
+                    .unwrap_or_default(),
+            );
+            points.push(position);
+            environment.update_var("Path", edit::path_text(&points));
+            environment.update_var("Ready", "True");
+        }
     }
 
     #[derive(Debug, PartialEq)]
@@ -735,6 +808,7 @@ pub fn is_screen_member_name(name: &str) -> bool {
         || name == EDITABLE_SCREEN_NAME
         || name == CHOOSE_AREA_NAME
         || name == CHOOSE_POINT_NAME
+        || name == CHOOSE_PATH_NAME
         || name == PLAY_SCREEN_NAME
 }
 
@@ -785,11 +859,11 @@ pub fn apply_event(
             true
         }
         Event::UpdateChore { id, chore } => {
-            members[id.member].todo_list[id.chore] = *chore.clone();
+            *play::chore_mut(&mut members[id.member].todo_list, id.chore) = *chore.clone();
             true
         }
         Event::MoveChoreUp { id } => {
-            if id.chore == 0 {
+            if id.chore == 0 || id.chore >= members[id.member].todo_list.len() {
                 false
             } else {
                 members[id.member].todo_list.swap(id.chore, id.chore - 1);
@@ -798,7 +872,7 @@ pub fn apply_event(
             }
         }
         Event::MoveChoreDown { id } => {
-            if id.chore == serial::CHORE_COUNT - 1 {
+            if id.chore + 1 >= members[id.member].todo_list.len() {
                 false
             } else {
                 members[id.member].todo_list.swap(id.chore, id.chore + 1);
@@ -807,56 +881,54 @@ pub fn apply_event(
             }
         }
         Event::MoveQuestionUp { id } => {
-            if id.question == 0 {
+            let chore = play::chore_mut(&mut members[id.member].todo_list, id.chore);
+            if id.question == 0 || id.question >= chore.questions.len() {
                 false
             } else {
-                members[id.member].todo_list[id.chore]
-                    .questions
-                    .swap(id.question, id.question - 1);
+                chore.questions.swap(id.question, id.question - 1);
                 context_variables.insert("Question Index".to_owned(), (id.question).to_string());
                 true
             }
         }
         Event::MoveQuestionDown { id } => {
-            if id.question == serial::QUESTION_COUNT - 1 {
+            let chore = play::chore_mut(&mut members[id.member].todo_list, id.chore);
+            if id.question + 1 >= chore.questions.len() {
                 false
             } else {
-                members[id.member].todo_list[id.chore]
-                    .questions
-                    .swap(id.question, id.question + 1);
+                chore.questions.swap(id.question, id.question + 1);
                 context_variables
                     .insert("Question Index".to_owned(), (id.question + 2).to_string());
                 true
             }
         }
         Event::MoveDemandUp { id } => {
-            if id.demand == 0 {
+            let chore = play::chore_mut(&mut members[id.member].todo_list, id.chore);
+            if id.demand == 0 || id.demand >= chore.demands.len() {
                 false
             } else {
-                members[id.member].todo_list[id.chore]
-                    .demands
-                    .swap(id.demand, id.demand - 1);
+                chore.demands.swap(id.demand, id.demand - 1);
                 context_variables.insert("Demand Index".to_owned(), (id.demand).to_string());
                 true
             }
         }
         Event::MoveDemandDown { id } => {
-            if id.demand == serial::DEMAND_COUNT - 1 {
+            let chore = play::chore_mut(&mut members[id.member].todo_list, id.chore);
+            if id.demand + 1 >= chore.demands.len() {
                 false
             } else {
-                members[id.member].todo_list[id.chore]
-                    .demands
-                    .swap(id.demand, id.demand + 1);
+                chore.demands.swap(id.demand, id.demand + 1);
                 context_variables.insert("Demand Index".to_owned(), (id.demand + 2).to_string());
                 true
             }
         }
         Event::UpdateQuestion { id, question } => {
-            members[id.member].todo_list[id.chore].questions[id.question] = question.clone();
+            *play::chore_mut(&mut members[id.member].todo_list, id.chore)
+                .question_mut(id.question) = question.clone();
             true
         }
         Event::UpdateDemand { id, demand } => {
-            members[id.member].todo_list[id.chore].demands[id.demand] = demand.clone();
+            *play::chore_mut(&mut members[id.member].todo_list, id.chore).demand_mut(id.demand) =
+                demand.clone();
             true
         }
         Event::AddCharacter { index, ch } => {
@@ -872,6 +944,22 @@ pub fn apply_event(
             members[*index].sprite = *to;
             true
         }
+        Event::SetStartState {
+            index,
+            from: _from,
+            to,
+        } => {
+            members[*index].state = to.clone();
+            true
+        }
+        Event::SetSolid {
+            index,
+            from: _from,
+            to,
+        } => {
+            members[*index].solid = *to;
+            true
+        }
         Event::AddNote {
             editing_position,
             note,
@@ -985,6 +1073,7 @@ pub fn apply_event(
                     .set_pixel(position.x as u32, position.y as u32, *to);
             }
             assets.texture.update(&assets.image);
+            assets.forget_masks();
             true
         }
     }
@@ -1022,7 +1111,7 @@ pub fn handle_events(
             },
             Event::UpdateChore { id, .. } => Event::UpdateChore {
                 id: *id,
-                chore: Box::new(subgame.members[id.member].todo_list[id.chore].clone()),
+                chore: Box::new(play::chore(&subgame.members[id.member].todo_list, id.chore)),
             },
             Event::MoveChoreUp { id } => Event::MoveChoreDown { id: *id },
             Event::MoveChoreDown { id } => Event::MoveChoreUp { id: *id },
@@ -1032,12 +1121,13 @@ pub fn handle_events(
             Event::MoveDemandDown { id } => Event::MoveDemandUp { id: *id },
             Event::UpdateQuestion { id, .. } => Event::UpdateQuestion {
                 id: *id,
-                question: subgame.members[id.member].todo_list[id.chore].questions[id.question]
-                    .clone(),
+                question: play::chore(&subgame.members[id.member].todo_list, id.chore)
+                    .question(id.question),
             },
             Event::UpdateDemand { id, .. } => Event::UpdateDemand {
                 id: *id,
-                demand: subgame.members[id.member].todo_list[id.chore].demands[id.demand].clone(),
+                demand: play::chore(&subgame.members[id.member].todo_list, id.chore)
+                    .demand(id.demand),
             },
             Event::AddCharacter { index, ch: _ch } => Event::RemoveCharacter { index: *index },
             Event::RemoveCharacter { index } => Event::AddCharacter {
@@ -1055,6 +1145,16 @@ pub fn handle_events(
                 from: *to,
                 to: *from,
             },
+            Event::SetStartState { index, from, to } => Event::SetStartState {
+                index: *index,
+                from: to.clone(),
+                to: from.clone(),
+            },
+            Event::SetSolid { index, from, to } => Event::SetSolid {
+                index: *index,
+                from: *to,
+                to: *from,
+            },
             Event::AddNote {
                 editing_position,
                 note,
@@ -1270,6 +1370,9 @@ async fn apply_menu_actions(
                     sink_player.sfx_sinks.clear();
                 }
                 audio_player.stop_record();
+                if let Some(difficulty) = editor.difficulty_before_preview.take() {
+                    environment.update_var("Difficulty", difficulty);
+                }
                 log::debug!("Stop!");
             }
             menu::Action::Quit => {
@@ -1329,6 +1432,7 @@ async fn apply_menu_actions(
                         game: name.to_string(),
                     },
                     &file_system,
+                    environment.rng.next_rng(),
                 )
                 .await?;
                 log::debug!("FADING OUT TO: {}", name);
@@ -1388,23 +1492,19 @@ async fn apply_menu_actions(
                 log::debug!("AFTER: {:?}", navigation.queue.links);
             }
             menu::Action::New => {
-                let bytes = bytes_from_dir("images", DEFAULT_IMAGE_FILENAME)
-                    .await
-                    .unwrap();
+                let bytes = bytes_from_dir("images", DEFAULT_IMAGE_FILENAME).await?;
                 let image_string = ImageString(BaseEncoder.encode(&bytes));
 
-                let bytes = bytes_from_dir("fonts", DEFAULT_FONT_FILENAME)
-                    .await
-                    .unwrap();
+                let bytes = bytes_from_dir("fonts", DEFAULT_FONT_FILENAME).await?;
                 let font_string = ImageString(BaseEncoder.encode(&bytes));
 
                 let size = get_typed_variable(&environment.context, "Game Size").unwrap();
-                let rng = SeededRng::new(macroquad::miniquad::date::now() as _);
+                let rng = environment.rng.next_rng();
 
                 *subgame = game_from_cartridge(
                     serial::Cartridge::new(size, image_string, font_string),
                     rng,
-                );
+                )?;
                 editor.selected_index = 0;
                 editor.index_tracker = 0;
                 editor.previous_hovered_indices = Vec::new();
@@ -1417,14 +1517,23 @@ async fn apply_menu_actions(
                     .to_string();
                 log::debug!("Loading: {}", game_filename);
                 // TODO: Use sub collection variable or something?
-                *subgame = play::Game::load(
+                let loaded = play::Game::load(
                     &Link {
                         collection: environment.context["Collection"].clone(),
                         game: game_filename.to_string(),
                     },
                     &file_system,
+                    environment.rng.next_rng(),
                 )
-                .await?;
+                .await;
+                *subgame = match loaded {
+                    Ok(loaded) => loaded,
+                    Err(e) => {
+                        log::error!("Could not load {}: {}", game_filename, e);
+                        subgame.error = Some(format!("Could not load {}: {}", game_filename, e));
+                        continue;
+                    }
+                };
                 editor.selected_index = 0;
                 editor.index_tracker = 0;
                 editor.previous_hovered_indices = Vec::new();
@@ -1440,6 +1549,8 @@ async fn apply_menu_actions(
                 );
 
                 environment.update_var_as_debug("Game Size", subgame.size);
+                environment.update_world_size_vars(subgame);
+                environment.update_difficulty_scaling_var(subgame);
                 environment.update_var_as_debug("Length", subgame.length);
                 environment.update_var("Game", game_filename);
             }
@@ -1453,18 +1564,19 @@ async fn apply_menu_actions(
             menu::Action::SetImageFile => {
                 let image_filename = &environment.context["Image File Name"];
                 let image_filename = format!("{}.png", image_filename);
-                let bytes = bytes_from_dir("images", &image_filename).await.unwrap();
+                let bytes = bytes_from_dir("images", &image_filename).await?;
                 subgame.assets.image_string = ImageString(BaseEncoder.encode(&bytes));
 
-                subgame.assets.texture = texture_from_bytes(&bytes).unwrap();
+                subgame.assets.texture = texture_from_bytes(&bytes)?;
                 subgame.assets.image = subgame.assets.texture.get_texture_data();
+                subgame.assets.forget_masks();
 
                 subgame.assets.filenames.image = Some(image_filename.to_owned());
             }
             menu::Action::SetMusicFile => {
                 let music_filename = &environment.context["Music File Name"];
                 let music_filename = format!("{}.ogg", music_filename);
-                let bytes = bytes_from_dir("music", &music_filename).await.unwrap();
+                let bytes = bytes_from_dir("music", &music_filename).await?;
                 subgame.assets.music_string = Some(SoundString(BaseEncoder.encode(&bytes)));
 
                 subgame.assets.music_data = Some(bytes);
@@ -1474,7 +1586,7 @@ async fn apply_menu_actions(
             menu::Action::PreviewMusic => {
                 let music_filename = &environment.context["Music File Name"];
                 let music_filename = format!("{}.ogg", music_filename);
-                let preview_music = bytes_from_dir("music", &music_filename).await.unwrap();
+                let preview_music = bytes_from_dir("music", &music_filename).await?;
                 audio_player.play_music(Some(preview_music))?;
             }
             menu::Action::StopMusic => {
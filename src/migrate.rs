@@ -0,0 +1,73 @@
+use serde_json::Value;
+
+use crate::err::WhyResult;
+
+// Each entry upgrades a cartridge from the version matching its index to the next one
+const MIGRATIONS: &[fn(&mut Value) -> WhyResult<()>] = &[migrate_0_to_1];
+
+pub const CURRENT_FORMAT_VERSION: usize = MIGRATIONS.len();
+
+pub fn format_version(cartridge: &Value) -> WhyResult<usize> {
+    match cartridge.get("format_version") {
+        None => Ok(0),
+        Some(version) => version
+            .as_u64()
+            .map(|version| version as usize)
+            .ok_or_else(|| format!("Invalid format version: {}", version).into()),
+    }
+}
+
+pub fn migrate(cartridge: &mut Value) -> WhyResult<()> {
+    if !cartridge.is_object() {
+        return Err("Cartridge is not a JSON object".into());
+    }
+
+    let version = format_version(cartridge)?;
+
+    if version > CURRENT_FORMAT_VERSION {
+        return Err(format!(
+            "Cartridge format version {} is newer than the supported version {}",
+            version, CURRENT_FORMAT_VERSION
+        )
+        .into());
+    }
+
+    for (from, migration) in MIGRATIONS.iter().enumerate().skip(version) {
+        migration(cartridge).map_err(|e| {
+            format!(
+                "Error migrating cartridge from version {} to {}: {}",
+                from,
+                from + 1,
+                e
+            )
+        })?;
+        cartridge["format_version"] = (from + 1).into();
+    }
+
+    Ok(())
+}
+
+// Version 0 was written before versions were checked, the shape is unchanged
+fn migrate_0_to_1(_cartridge: &mut Value) -> WhyResult<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migrate_unversioned() {
+        let mut cartridge = serde_json::json!({ "members": [] });
+        migrate(&mut cartridge).unwrap();
+
+        assert_eq!(format_version(&cartridge).unwrap(), CURRENT_FORMAT_VERSION);
+    }
+
+    #[test]
+    fn test_migrate_future_version() {
+        let mut cartridge = serde_json::json!({ "format_version": CURRENT_FORMAT_VERSION + 1 });
+
+        assert!(migrate(&mut cartridge).is_err());
+    }
+}
@@ -1,11 +1,18 @@
 //use crate::nav::Link;
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
+use green::{
+    err::WhyResult,
+    lint::lint,
+    migrate::{format_version, migrate, CURRENT_FORMAT_VERSION},
+    serial::Cartridge,
+};
 use serde_json::Value;
 
 fn main() {
-    if let Some(arg) = std::env::args().nth(1) {
-        if arg == "package" {
+    match std::env::args().nth(1).as_deref() {
+        Some("package") => {
             let mut memfs = HashMap::new();
             println!("Packaging games");
             {
@@ -15,8 +22,9 @@ fn main() {
                     let s = p.to_str().unwrap();
 
                     // To minimise
-                    let cartridge: Value =
+                    let mut cartridge: Value =
                         serde_json::from_str(&std::fs::read_to_string(&p).unwrap()).unwrap();
+                    migrate(&mut cartridge).unwrap();
 
                     memfs.insert(s.to_string(), serde_json::to_string(&cartridge).unwrap());
                 }
@@ -25,7 +33,89 @@ fn main() {
             std::fs::write("fs.json", s).unwrap_or_else(|e| println!("{}", e));
             std::process::exit(0);
         }
+        Some("migrate") => {
+            println!(
+                "Migrating games to format version {}",
+                CURRENT_FORMAT_VERSION
+            );
+            let mut failed = false;
+            for p in cartridge_paths_or_exit() {
+                match migrate_file(&p) {
+                    Ok(Some(version)) => {
+                        println!("{}: {} -> {}", p.display(), version, CURRENT_FORMAT_VERSION)
+                    }
+                    Ok(None) => {}
+                    Err(e) => {
+                        println!("{}: {}", p.display(), e);
+                        failed = true;
+                    }
+                }
+            }
+            std::process::exit(if failed { 1 } else { 0 });
+        }
+        Some("lint") => {
+            let mut problem_count = 0;
+            for p in cartridge_paths_or_exit() {
+                let diagnostics = std::fs::read_to_string(&p)
+                    .map_err(|e| e.into())
+                    .and_then(|contents| Cartridge::from_file_contents(&contents))
+                    .map(|cartridge| lint(&cartridge));
+                match diagnostics {
+                    Ok(diagnostics) => {
+                        for diagnostic in &diagnostics {
+                            println!("{}: {}", p.display(), diagnostic);
+                        }
+                        problem_count += diagnostics.len();
+                    }
+                    Err(e) => {
+                        println!("{}: {}", p.display(), e);
+                        problem_count += 1;
+                    }
+                }
+            }
+            println!("{} problems found", problem_count);
+            std::process::exit(if problem_count > 0 { 1 } else { 0 });
+        }
+        _ => {}
     }
 
     println!("HEY");
 }
+
+fn cartridge_paths_or_exit() -> Vec<PathBuf> {
+    cartridge_paths().unwrap_or_else(|e| {
+        println!("Couldn't list collections: {}", e);
+        std::process::exit(1);
+    })
+}
+
+fn cartridge_paths() -> WhyResult<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+    for collection in std::fs::read_dir("collections/")? {
+        let collection = collection?.path();
+        if !collection.is_dir() {
+            continue;
+        }
+        for path in std::fs::read_dir(&collection)? {
+            let p = path?.path();
+            if p.extension() == Some(std::ffi::OsStr::new("json")) {
+                paths.push(p);
+            }
+        }
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+// Returns the old version if the file needed migrating
+fn migrate_file(path: &Path) -> WhyResult<Option<usize>> {
+    let contents = std::fs::read_to_string(path)?;
+    let version = format_version(&serde_json::from_str(&contents)?)?;
+    if version == CURRENT_FORMAT_VERSION {
+        return Ok(None);
+    }
+
+    let cartridge = Cartridge::from_file_contents(&contents)?;
+    std::fs::write(path, serde_json::to_string_pretty(&cartridge)?)?;
+    Ok(Some(version))
+}
@@ -119,8 +119,8 @@ impl Rect {
     pub fn collides(self, other: Rect) -> bool {
         self.min.x < other.max.x
             && self.max.x > other.min.x
-            && self.min.y > other.max.y
-            && self.max.y < other.min.y
+            && self.min.y < other.max.y
+            && self.max.y > other.min.y
     }
 
     pub fn intersecting_rect(self, other: Rect) -> Option<Rect> {
@@ -137,6 +137,14 @@ impl Rect {
         }
     }
 
+    // The smallest rect covering both
+    pub fn union(self, other: Rect) -> Rect {
+        Rect::new(
+            Position::new(self.min.x.min(other.min.x), self.min.y.min(other.min.y)),
+            Position::new(self.max.x.max(other.max.x), self.max.y.max(other.max.y)),
+        )
+    }
+
     // TODO: Has less meaning because rounded
     /*pub fn top_left(self) -> Position {
         Position::new(self.x - self.w as i32 / 2, self.y - self.h as i32 / 2)
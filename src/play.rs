@@ -1,33 +1,36 @@
 // TODO: Module boundaries? SHouldn't reference macroquad here?
-use super::art::{Sprite, SpriteSize};
+use super::art::{Sprite, SpriteSize, Transform};
 use super::colours;
 use super::common::Speed;
 use super::pixels;
 use super::serial::{
-    self, AssetFilenames, Axis, Cartridge, CollisionWith, Demand, Direction, Hover, ImageString,
-    IntroText, JumpLocation, Motion, MovementHandling, Music, Question, RoamType, SoundString,
-    Switch, When, WhichButton, WinStatus, DEMAND_COUNT, QUESTION_COUNT,
+    self, AssetFilenames, Axis, Cartridge, CollisionWith, Demand, Direction, Group, Hover,
+    ImageString, IntroText, JumpLocation, Motion, MovementHandling, Music, ParticleStyle,
+    PathLooping, Question, RoamType, SoundString, Switch, When, WhichButton, WinStatus, WorldSize,
 };
 use super::texture_from_bytes;
 use super::Colour;
 use super::FilterMode;
-use crate::coll::{is_adjusted_subsection_square_active, CollisionObject};
+use crate::coll::{self, is_adjusted_subsection_square_active, CollisionObject, Mask, SpatialHash};
 use crate::doodle::DrawTool;
 use crate::drawer::{self, sheet_source_rect, sprite_size_in_pixels, Camera};
 use crate::edit::{
-    chore_index_from_context, demand_from_context, demand_index_from_context, fancy_question_text,
-    get_typed_variable, max_var_per_page, offset_for_page, padded_len, question_from_context,
-    question_index_from_context, sprite_from_context, Editor, Fancy,
+    chore_index_from_context, demand_from_context, demand_index_from_context,
+    fancy_question_text_for_chore, get_typed_variable, growable_index, growable_len,
+    index_from_context, max_var_per_page, offset_for_page, padded_len, path_text,
+    question_from_context, question_index_from_context, sprite_from_context, Editor,
 };
 use crate::err::WhyResult;
 use crate::history::Event;
-use crate::inp::{Button, Mouse};
+use crate::inp::{control_button, key_button, Mouse, RepeatableButton};
 use crate::meta::{
-    Environment, CHOOSE_AREA_NAME, CHOOSE_POINT_NAME, EDITABLE_SCREEN_NAME, MUSIC_MAKER_NAME,
-    OUTER_CENTRE, PLAY_SCREEN_NAME,
+    Environment, CHOOSE_AREA_NAME, CHOOSE_PATH_NAME, CHOOSE_POINT_NAME, EDITABLE_SCREEN_NAME,
+    MUSIC_MAKER_NAME, OUTER_CENTRE, PLAY_SCREEN_NAME,
 };
+use crate::migrate::CURRENT_FORMAT_VERSION;
 use crate::music::{self, MusicMaker};
 use crate::nav::{Link, Navigation};
+use crate::rend::headless_texture;
 use crate::seeded_rng::SeededRng;
 use crate::seeded_rng::{ChooseRandom, RandomRange};
 use crate::serial::Shortcut;
@@ -35,13 +38,19 @@ use crate::{menu, FileSystem};
 use base64::engine::general_purpose::STANDARD_NO_PAD as BaseEncoder;
 use base64::Engine;
 use macroquad::{
+    input::KeyCode,
     logging as log,
     math::{Rect, Vec2},
     texture::{Image, Texture2D},
 };
+use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fmt;
+use std::rc::Rc;
 
 pub use super::anim::Animation;
+pub use super::serial::DifficultyLevel;
 pub use super::serial::GameSize as Size;
 pub use super::serial::Length;
 
@@ -55,8 +64,18 @@ pub struct BitmapFont {
 
 impl BitmapFont {
     pub fn new(texture: Texture2D) -> BitmapFont {
-        let mut font_image = texture.get_texture_data();
+        Self::from_image(texture.get_texture_data(), |font_image| {
+            let texture = Texture2D::from_image(font_image);
+            texture.set_filter(FilterMode::Nearest);
+            texture
+        })
+    }
+
+    pub fn headless(font_image: Image) -> BitmapFont {
+        Self::from_image(font_image, |_| headless_texture())
+    }
 
+    fn from_image(mut font_image: Image, to_texture: impl Fn(&Image) -> Texture2D) -> BitmapFont {
         let is_key = |colour: Colour| {
             colour.a == 1.0 && colour.r == 1.0 && colour.g == 0.0 && colour.b == 1.0
         };
@@ -122,11 +141,7 @@ impl BitmapFont {
             }
         }
 
-        let font_texture = {
-            let texture = Texture2D::from_image(&font_image);
-            texture.set_filter(FilterMode::Nearest);
-            texture
-        };
+        let font_texture = to_texture(&font_image);
 
         BitmapFont {
             texture: font_texture,
@@ -243,33 +258,63 @@ pub struct Assets {
     pub music_string: Option<SoundString>,
     pub music_data: Option<Vec<u8>>,
     pub filenames: AssetFilenames,
+    // Made the first time each sprite is checked for collisions
+    pub masks: RefCell<HashMap<(Sprite, Transform), Rc<Mask>>>,
 }
 
 impl Assets {
+    pub fn mask(&self, sprite: Sprite, transform: Transform) -> Rc<Mask> {
+        self.masks
+            .borrow_mut()
+            .entry((sprite, transform))
+            .or_insert_with(|| {
+                let mask = Mask::from_section(&self.image, sheet_source_rect(sprite));
+                Rc::new(
+                    mask.flipped(transform.flip_x, transform.flip_y)
+                        .rotated(transform.rotation.quarter_turns())
+                        .scaled(transform.scale),
+                )
+            })
+            .clone()
+    }
+
+    // Has to be called whenever the sprite sheet image is changed
+    pub fn forget_masks(&self) {
+        self.masks.borrow_mut().clear();
+    }
+
     pub fn from_strings(
         image_string: ImageString,
         font_string: ImageString,
         music_string: Option<SoundString>,
         filenames: AssetFilenames,
-    ) -> Assets {
-        let bytes = BaseEncoder.decode(&image_string.0).unwrap();
+    ) -> WhyResult<Assets> {
+        let bytes = BaseEncoder
+            .decode(&image_string.0)
+            .map_err(|e| format!("Error decoding image: {:?}", e))?;
         //let image_string = ImageString(BaseEncoder.encode(&bytes));
-        let texture = texture_from_bytes(&bytes).unwrap();
+        let texture = texture_from_bytes(&bytes)?;
         let image = texture.get_texture_data();
 
-        let bytes = BaseEncoder.decode(font_string.0).unwrap();
+        let bytes = BaseEncoder
+            .decode(font_string.0)
+            .map_err(|e| format!("Error decoding font: {:?}", e))?;
         let font_string = ImageString(BaseEncoder.encode(&bytes));
-        let font_texture = texture_from_bytes(&bytes).unwrap();
+        let font_texture = texture_from_bytes(&bytes)?;
         let font = BitmapFont::new(font_texture);
 
         let mut music_data = None;
         if let Some(s) = &music_string {
-            music_data = Some(BaseEncoder.decode(&s.0).unwrap());
+            music_data = Some(
+                BaseEncoder
+                    .decode(&s.0)
+                    .map_err(|e| format!("Error decoding music: {:?}", e))?,
+            );
         }
 
         let _render_target = macroquad::texture::render_target(512, 512);
 
-        Assets {
+        Ok(Assets {
             texture,
             image,
             image_string,
@@ -278,7 +323,47 @@ impl Assets {
             music_string,
             music_data,
             filenames,
+            masks: Default::default(),
+        })
+    }
+
+    pub fn headless_from_strings(
+        image_string: ImageString,
+        font_string: ImageString,
+        music_string: Option<SoundString>,
+        filenames: AssetFilenames,
+    ) -> WhyResult<Assets> {
+        let bytes = BaseEncoder
+            .decode(&image_string.0)
+            .map_err(|e| format!("Error decoding image: {:?}", e))?;
+        let image = Image::from_file_with_format(&bytes, None)?;
+
+        let bytes = BaseEncoder
+            .decode(font_string.0)
+            .map_err(|e| format!("Error decoding font: {:?}", e))?;
+        let font_string = ImageString(BaseEncoder.encode(&bytes));
+        let font = BitmapFont::headless(Image::from_file_with_format(&bytes, None)?);
+
+        let mut music_data = None;
+        if let Some(s) = &music_string {
+            music_data = Some(
+                BaseEncoder
+                    .decode(&s.0)
+                    .map_err(|e| format!("Error decoding music: {:?}", e))?,
+            );
         }
+
+        Ok(Assets {
+            texture: headless_texture(),
+            image,
+            image_string,
+            font,
+            font_string,
+            music_string,
+            music_data,
+            filenames,
+            masks: Default::default(),
+        })
     }
 
     /*pub async fn load(image_filename: &str, font_filename: &str) -> Assets {
@@ -310,19 +395,208 @@ pub struct Game {
     pub size: Size,
     pub length: Length,
     pub win_status: WinStatus,
-    pub triggered_questions: HashSet<QuestionId>,
+    // Along with the leaf inside the question, see is_question_true
+    pub triggered_questions: HashSet<(QuestionId, usize)>,
     pub frame_number: usize,
     pub intro_text: IntroText,
     pub rng: SeededRng,
+    // Numbers that haven't been set yet are 0
+    pub numbers: HashMap<String, i32>,
+    // Set when a chore fails, the game stops updating until it is dismissed
+    pub error: Option<String>,
+    // Counts up with every spawn so instance names are never reused
+    pub spawn_count: usize,
+    // Destroyed members, kept so instances can still be spawned from them
+    pub templates: HashMap<String, Member>,
+    // Only for show, nothing can collide with or ask about particles
+    pub particles: Vec<Particle>,
+    // None keeps the world the same size as the screen
+    pub world_size: Option<WorldSize>,
+    pub camera: WorldCamera,
+    pub scales_with_difficulty: bool,
+}
+
+// Scroll is the world position shown in the top left of the screen
+#[derive(Clone, Debug, Default)]
+pub struct WorldCamera {
+    pub scroll: Vec2,
+    pub aim: CameraAim,
+}
+
+#[derive(Clone, Debug, Default)]
+pub enum CameraAim {
+    #[default]
+    Still,
+    Follow {
+        name: String,
+        dead_zone: pixels::Size,
+        bounds: Option<pixels::Rect>,
+    },
+    PanTo {
+        point: Vec2,
+        speed: f32,
+    },
+}
+
+#[derive(Clone, Debug)]
+pub struct Particle {
+    pub position: Vec2,
+    pub velocity: Vec2,
+    pub style: ParticleStyle,
+    pub age: u32,
+    pub lifetime: u32,
+}
+
+impl Particle {
+    // Fades out over its lifetime
+    pub fn alpha(&self) -> f32 {
+        1.0 - self.age as f32 / self.lifetime.max(1) as f32
+    }
+}
+
+const PARTICLE_GRAVITY: f32 = 0.05;
+
+fn emit_particles(
+    position: Vec2,
+    style: ParticleStyle,
+    count: u32,
+    speed: Speed,
+    spread: u32,
+    lifetime: u32,
+    rng: &mut SeededRng,
+) -> Vec<Particle> {
+    let speed_constant = 2.0;
+    let speed_value = speed_constant * speed.multiplier();
+    let half_spread = (spread.min(360) as f32 / 2.0).to_radians();
+    (0..count)
+        .map(|_| {
+            let angle =
+                -std::f32::consts::FRAC_PI_2 + rng.number_in_range(-half_spread, half_spread);
+            let speed = rng.number_in_range(speed_value / 2.0, speed_value);
+            Particle {
+                position,
+                velocity: Vec2::new(angle.cos(), angle.sin()) * speed,
+                style,
+                age: 0,
+                lifetime,
+            }
+        })
+        .collect()
+}
+
+fn update_particles(particles: &mut Vec<Particle>) {
+    for particle in particles.iter_mut() {
+        particle.position += particle.velocity;
+        particle.velocity.y += PARTICLE_GRAVITY;
+        particle.age += 1;
+    }
+    particles.retain(|particle| particle.age < particle.lifetime);
+}
+
+// Only the distance outside of the dead zone is scrolled
+fn dead_zone_push(offset: f32, half_dead_zone: f32) -> f32 {
+    if offset > half_dead_zone {
+        offset - half_dead_zone
+    } else if offset < -half_dead_zone {
+        offset + half_dead_zone
+    } else {
+        0.0
+    }
+}
+
+// Keeps the screen inside the bounds, a bounds smaller than the screen pins it to the top left
+fn clamp_scroll(scroll: f32, min: i32, max: i32, screen: u32) -> f32 {
+    let max = (max - screen as i32).max(min);
+    scroll.clamp(min as f32, max as f32)
+}
+
+fn update_camera(game: &mut Game) {
+    let screen_size = game.size.screen_size();
+    let world_size = game.world_size();
+    let half_screen = Vec2::new(screen_size.w as f32 / 2.0, screen_size.h as f32 / 2.0);
+    let mut bounds = pixels::Rect::aabb(0, 0, world_size.w as i32, world_size.h as i32);
+
+    let mut has_arrived = false;
+    match &game.camera.aim {
+        CameraAim::Still => {}
+        CameraAim::Follow {
+            name,
+            dead_zone,
+            bounds: follow_bounds,
+        } => {
+            if let Some(member) = game.members.iter().find(|member| &member.name == name) {
+                let offset = member.position - (game.camera.scroll + half_screen);
+                game.camera.scroll.x += dead_zone_push(offset.x, dead_zone.w as f32 / 2.0);
+                game.camera.scroll.y += dead_zone_push(offset.y, dead_zone.h as f32 / 2.0);
+            }
+            if let Some(follow_bounds) = follow_bounds {
+                bounds = *follow_bounds;
+            }
+        }
+        CameraAim::PanTo { point, speed } => {
+            let to_target = *point - half_screen - game.camera.scroll;
+            if to_target.length() <= *speed {
+                game.camera.scroll += to_target;
+                has_arrived = true;
+            } else {
+                game.camera.scroll += to_target.normalize() * *speed;
+            }
+        }
+    }
+    if has_arrived {
+        game.camera.aim = CameraAim::Still;
+    }
+
+    game.camera.scroll.x = clamp_scroll(
+        game.camera.scroll.x,
+        bounds.min.x,
+        bounds.max.x,
+        screen_size.w,
+    );
+    game.camera.scroll.y = clamp_scroll(
+        game.camera.scroll.y,
+        bounds.min.y,
+        bounds.max.y,
+        screen_size.h,
+    );
 }
 
 impl Game {
-    pub async fn load(link: &Link, file_system: &FileSystem) -> WhyResult<Game> {
+    // Never smaller than the screen
+    pub fn world_size(&self) -> pixels::Size {
+        let screen_size = self.size.screen_size();
+        match self.world_size {
+            Some(WorldSize { width, height }) => {
+                pixels::Size::new(width.max(screen_size.w), height.max(screen_size.h))
+            }
+            None => screen_size,
+        }
+    }
+
+    pub fn scroll(&self) -> pixels::Position {
+        pixels::Position::new(
+            self.camera.scroll.x.round() as i32,
+            self.camera.scroll.y.round() as i32,
+        )
+    }
+
+    pub fn members_in_drawing_order(&self) -> Vec<&Member> {
+        in_drawing_order(&self.members)
+    }
+
+    pub async fn load(link: &Link, file_system: &FileSystem, rng: SeededRng) -> WhyResult<Game> {
         let cartridge = Cartridge::load(link, file_system).await?;
 
-        let rng = SeededRng::new(macroquad::miniquad::date::now() as u64);
+        game_from_cartridge(cartridge, rng)
+    }
 
-        Ok(game_from_cartridge(cartridge, rng))
+    // Keeps a broken game on screen with its error instead of ending the session
+    pub fn halt_on_error<T: Default>(&mut self, result: WhyResult<T>) -> T {
+        result.unwrap_or_else(|e| {
+            log::error!("{}", e);
+            self.error = Some(e.to_string());
+            T::default()
+        })
     }
 
     pub fn screen_position(&self) -> pixels::Position {
@@ -342,6 +616,7 @@ impl Game {
             EDITABLE_SCREEN_NAME,
             CHOOSE_AREA_NAME,
             CHOOSE_POINT_NAME,
+            CHOOSE_PATH_NAME,
             PLAY_SCREEN_NAME,
             MUSIC_MAKER_NAME,
             "{Edit Sprite}",
@@ -370,28 +645,283 @@ pub struct Member {
     pub animation: Animation<Sprite>,
     pub text: Text,
     pub todo_list: Vec<Chore>,
+    // How many frames each running timer has been running for
+    pub timers: HashMap<String, usize>,
+    // Set on spawned instances, which are left out when the game is saved
+    pub template: Option<String>,
+    pub tags: Vec<String>,
+    pub solid: bool,
+    pub layer: i32,
+    pub hidden: bool,
+    pub transform: Transform,
+    pub tint: Option<Colour>,
+    // Messages sent last frame, each one is only received for a single frame
+    pub inbox: HashSet<String>,
+    pub state: Option<String>,
+    // Only true for the first frame after changing state
+    pub just_entered_state: bool,
+    // Chores that are waiting to carry on
+    pub sequences: Vec<Sequence>,
+}
+
+// The demands left over after a chore waits, run once frames_left counts down to 0.
+// Counted down rather than compared to the frame number, which goes back to 0 after a pause
+#[derive(Clone, Debug)]
+pub struct Sequence {
+    pub chore: usize,
+    pub frames_left: usize,
+    pub demands: Vec<Demand>,
+}
+
+// Each level of difficulty is one speed faster
+fn scaled_for_difficulty(mut demand: Demand, difficulty: DifficultyLevel) -> Demand {
+    match &mut demand {
+        Demand::Animate { speed, .. }
+        | Demand::Motion(
+            Motion::Go { speed, .. }
+            | Motion::GoToPoint { speed, .. }
+            | Motion::FollowPath { speed, .. }
+            | Motion::Roam { speed, .. }
+            | Motion::Target { speed, .. }
+            | Motion::TargetTagged { speed, .. },
+        ) => *speed = speed.faster_by(difficulty as usize),
+        _ => {}
+    }
+    demand
 }
 
-fn get_member<'a>(members: &'a [Member], name: &str) -> &'a Member {
-    members.iter().find(|member| member.name == name).unwrap()
+// Adds the demands up to the first wait, and returns the rest to carry on after it
+fn run_until_wait(
+    chore: usize,
+    demands: &[Demand],
+    runnable: &mut Vec<(usize, Demand)>,
+) -> Option<Sequence> {
+    for (index, demand) in demands.iter().enumerate() {
+        match demand {
+            Demand::None => {}
+            Demand::Wait { frames } => {
+                return Some(Sequence {
+                    chore,
+                    frames_left: *frames,
+                    demands: demands[index + 1..].to_vec(),
+                });
+            }
+            demand => runnable.push((chore, demand.clone())),
+        }
+    }
+    None
+}
+
+impl Member {
+    // Spawned instances also answer to the name of the member they were spawned from
+    pub fn is_called(&self, name: &str) -> bool {
+        self.name == name || self.template.as_deref() == Some(name)
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t == tag)
+    }
+
+    pub fn is_in_state(&self, state: &str) -> bool {
+        self.state.as_deref() == Some(state)
+    }
+
+    // Entering the state it's already in doesn't count as just entering it
+    fn enter_state(&mut self, state: String) {
+        if !self.is_in_state(&state) {
+            self.state = Some(state);
+            self.just_entered_state = true;
+        }
+    }
+
+    pub fn has_timer_passed(&self, name: &str, frames: usize) -> bool {
+        self.timers
+            .get(name)
+            .is_some_and(|&elapsed| elapsed >= frames)
+    }
+
+    // Counted up rather than compared to the frame number, which goes back to 0 after a pause
+    fn count_timers(&mut self) {
+        for elapsed in self.timers.values_mut() {
+            *elapsed += 1;
+        }
+    }
+
+    fn is_waiting_on(&self, chore: usize) -> bool {
+        self.sequences
+            .iter()
+            .any(|sequence| sequence.chore == chore)
+    }
+
+    // Counts down every waiting chore and runs the demands of those that are done waiting
+    fn resume_sequences(&mut self) -> Vec<(usize, Demand)> {
+        for sequence in self.sequences.iter_mut() {
+            sequence.frames_left = sequence.frames_left.saturating_sub(1);
+        }
+        let (due, waiting): (Vec<Sequence>, Vec<Sequence>) = self
+            .sequences
+            .drain(..)
+            .partition(|sequence| sequence.frames_left == 0);
+        self.sequences = waiting;
+        let mut runnable = Vec::new();
+        for sequence in due {
+            if let Some(rest) = run_until_wait(sequence.chore, &sequence.demands, &mut runnable) {
+                self.sequences.push(rest);
+            }
+        }
+        runnable
+    }
+}
+
+// Replaces every inbox so messages that weren't sent again this frame are forgotten
+fn deliver_messages(members: &mut [Member], sent: Vec<(String, Option<String>)>) {
+    for member in members.iter_mut() {
+        member.inbox.clear();
+    }
+    for (message, to) in sent {
+        for member in members.iter_mut() {
+            if to.as_deref().is_none_or(|name| member.is_called(name)) {
+                member.inbox.insert(message.clone());
+            }
+        }
+    }
+}
+
+// Lower layers first, members on the same layer keep their list order
+fn in_drawing_order(members: &[Member]) -> Vec<&Member> {
+    let mut members: Vec<&Member> = members.iter().collect();
+    members.sort_by_key(|member| member.layer);
+    members
+}
+
+fn get_member<'a>(members: &'a [Member], name: &str) -> WhyResult<&'a Member> {
+    members
+        .iter()
+        .find(|member| member.name == name)
+        .ok_or_else(|| format!("No member called '{}'", name).into())
+}
+
+fn get_template<'a>(
+    members: &'a [Member],
+    templates: &'a HashMap<String, Member>,
+    name: &str,
+) -> WhyResult<&'a Member> {
+    get_member(members, name).or_else(|e| templates.get(name).ok_or(e))
+}
+
+// All needs at least one member, otherwise an empty group would always match
+fn does_group_match<T, F>(group: Group, members: impl Iterator<Item = T>, is_match: F) -> bool
+where
+    F: FnMut(T) -> bool,
+{
+    let mut members = members.peekable();
+    match group {
+        Group::Any => members.any(is_match),
+        Group::All => members.peek().is_some() && members.all(is_match),
+    }
+}
+
+fn is_switch_set_to(member_switch: Switch, switch: Switch) -> bool {
+    if switch == Switch::Off {
+        member_switch == Switch::SwitchedOff || member_switch == Switch::Off
+    } else if switch == Switch::On {
+        member_switch == Switch::SwitchedOn || member_switch == Switch::On
+    } else {
+        member_switch == switch
+    }
+}
+
+fn applied_switch(switch: Switch) -> Switch {
+    // TODO: Constrain switch to On/Off
+    match switch {
+        Switch::On | Switch::SwitchedOn => Switch::SwitchedOn,
+        Switch::Off | Switch::SwitchedOff => Switch::SwitchedOff,
+    }
 }
 
 #[derive(Clone, Debug, Default)]
 pub struct Chore {
-    pub questions: [Question; QUESTION_COUNT],
-    pub demands: [Demand; DEMAND_COUNT],
+    pub label: Option<String>,
+    pub state: Option<String>,
+    pub questions: Vec<Question>,
+    pub demands: Vec<Demand>,
+}
+
+// Anything past the end of a chore or todo list reads as None, and is filled in when written to
+impl Chore {
+    pub fn question(&self, index: usize) -> Question {
+        self.questions.get(index).cloned().unwrap_or_default()
+    }
+
+    pub fn demand(&self, index: usize) -> Demand {
+        self.demands.get(index).cloned().unwrap_or_default()
+    }
+
+    pub fn question_mut(&mut self, index: usize) -> &mut Question {
+        if index >= self.questions.len() {
+            self.questions.resize(index + 1, Question::None);
+        }
+        &mut self.questions[index]
+    }
+
+    pub fn demand_mut(&mut self, index: usize) -> &mut Demand {
+        if index >= self.demands.len() {
+            self.demands.resize(index + 1, Demand::None);
+        }
+        &mut self.demands[index]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.questions
+            .iter()
+            .all(|question| *question == Question::None)
+            && self.demands.iter().all(|demand| *demand == Demand::None)
+    }
+
+    // Joining onto a group of the same kind adds to it rather than nesting another group
+    pub fn group_question_with_next(&mut self, index: usize, group: Group) -> bool {
+        if index + 1 >= self.questions.len() {
+            return false;
+        }
+
+        let next = self.questions.remove(index + 1);
+        let question = &mut self.questions[index];
+        match (question, group) {
+            (Question::AnyOf(questions), Group::Any) | (Question::AllOf(questions), Group::All) => {
+                questions.push(next)
+            }
+            (question, _) => {
+                let first = std::mem::take(question);
+                *question = match group {
+                    Group::Any => Question::AnyOf(vec![first, next]),
+                    Group::All => Question::AllOf(vec![first, next]),
+                };
+            }
+        }
+        true
+    }
+
+    // The questions in the group take its place in the list
+    pub fn ungroup_question(&mut self, index: usize) -> bool {
+        match self.question(index) {
+            Question::AnyOf(questions) | Question::AllOf(questions) => {
+                self.questions.splice(index..=index, questions);
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+pub fn chore(todo_list: &[Chore], index: usize) -> Chore {
+    todo_list.get(index).cloned().unwrap_or_default()
 }
 
-pub fn default_todo_list() -> Vec<Chore> {
-    // TODO: Simpler way?
-    vec![
-        Chore::default(),
-        Chore::default(),
-        Chore::default(),
-        Chore::default(),
-        Chore::default(),
-        Chore::default(),
-    ]
+pub fn chore_mut(todo_list: &mut Vec<Chore>, index: usize) -> &mut Chore {
+    if index >= todo_list.len() {
+        todo_list.resize(index + 1, Chore::default());
+    }
+    &mut todo_list[index]
 }
 
 #[derive(Clone, Debug, Default)]
@@ -429,6 +959,14 @@ pub enum ActiveMotion {
         point: pixels::Position,
         speed: Speed,
     },
+    // Heading for points[next], past the end once a path that plays once is finished
+    FollowPath {
+        points: Vec<pixels::Position>,
+        speed: Speed,
+        looping: PathLooping,
+        next: usize,
+        forwards: bool,
+    },
     Target {
         name: String,
         offset: Vec2,
@@ -438,6 +976,46 @@ pub enum ActiveMotion {
         name: String,
         offset: Vec2,
     },
+    Physics {
+        velocity: Vec2,
+        gravity: f32,
+        drag: f32,
+        bounciness: f32,
+        bounds: Option<pixels::Rect>,
+    },
+}
+
+impl ActiveMotion {
+    fn handling(&self) -> Option<MovementHandling> {
+        match self {
+            ActiveMotion::Wiggle { handling, .. }
+            | ActiveMotion::Insect { handling, .. }
+            | ActiveMotion::Reflect { handling, .. }
+            | ActiveMotion::Bounce { handling, .. } => Some(handling.clone()),
+            _ => None,
+        }
+    }
+
+    // Anything with its own velocity heads back the way it came
+    fn rebound(&mut self, blocked: Blocked) {
+        let (velocity, scale) = match self {
+            ActiveMotion::Insect { velocity, .. }
+            | ActiveMotion::Reflect { velocity, .. }
+            | ActiveMotion::Bounce { velocity, .. } => (velocity, 1.0),
+            ActiveMotion::Physics {
+                velocity,
+                bounciness,
+                ..
+            } => (velocity, *bounciness),
+            _ => return,
+        };
+        if blocked.x {
+            velocity.x = -velocity.x * scale;
+        }
+        if blocked.y {
+            velocity.y = -velocity.y * scale;
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Default)]
@@ -465,23 +1043,18 @@ impl Text {
     }
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Default)]
-#[repr(usize)]
-pub enum DifficultyLevel {
-    #[default]
-    Normal = 0,
-    Challenge = 1,
-    Tough = 2,
-}
-
 pub enum SoundQueue {
     Ready { sounds: HashSet<String> },
     Stopped,
 }
 
+// About the size of a small sprite, backgrounds just cover more cells
+const BROAD_PHASE_CELL_SIZE: i32 = 32;
+
 pub fn update_game(
     game: &mut Game,
     mouse: Mouse,
+    keyboard: &HashMap<KeyCode, RepeatableButton>,
     sounds_to_play: &mut SoundQueue,
     editor: &mut Editor,
     environment: &mut Environment,
@@ -491,350 +1064,406 @@ pub fn update_game(
     subgame: Option<&Game>,
     // TODO: Into some UpdateGameInputStruct
     shortcuts: &HashSet<Shortcut>,
-) -> (Vec<Event>, Vec<menu::Action>) {
+) -> WhyResult<(Vec<Event>, Vec<menu::Action>)> {
     let mut events_to_apply = Vec::new();
     let mut menu_actions = Vec::new();
 
-    let mut actions: Vec<Vec<Demand>> = Vec::new();
-    for (member_index, member) in game.members.iter().enumerate() {
-        let mut requested_demands: Vec<Demand> = Vec::new();
-
-        for (chore_index, chore) in member.todo_list.iter().enumerate() {
-            let mut triggered = true;
-            for (question_index, question) in chore.questions.iter().enumerate() {
-                triggered = triggered
-                    && match question {
-                        Question::None => true,
-                        Question::IsTimeAt(When::Start) => game.frame_number == 0,
-                        Question::IsTimeAt(When::End) => match game.length {
-                            // TODO: Constants, maybe sub 1?
-                            Length::Short => game.frame_number == 240,
-                            Length::Long => game.frame_number == 480,
-                            Length::Infinite => false,
-                        },
-                        Question::IsTimeAt(When::Exact { time }) => game.frame_number == *time * 5,
-                        Question::IsTimeAt(When::Random { start, end }) => {
-                            let question_id =
-                                QuestionId::new(member_index, chore_index, question_index);
-                            if game.triggered_questions.contains(&question_id) {
-                                false
-                            } else {
-                                let frame_number = game
-                                    .rng
-                                    .number_in_range((*start).max(game.frame_number), *end);
-                                let triggered = game.frame_number == frame_number;
-                                if triggered {
-                                    game.triggered_questions.insert(question_id);
-                                }
-                                triggered
-                            }
-                        }
-                        Question::IsMouseInteracting {
-                            which,
-                            state,
-                            hover,
-                        } => {
-                            let constraint = match hover {
-                                Hover::TopMember => game
-                                    .members
-                                    .iter()
-                                    .rev()
-                                    .find(|other_member| {
-                                        if let SpriteSize::Empty = other_member.sprite.size {
-                                            is_position_in_text(
-                                                mouse.position,
-                                                other_member.position,
-                                                &game.assets.font,
-                                                &other_member.text.contents,
-                                            )
-                                        } else {
-                                            is_position_in_member_image(
-                                                mouse.position,
-                                                other_member,
-                                                &game.assets.image,
-                                            )
-                                        }
-                                    })
-                                    .map(|top| std::ptr::eq(member, top))
-                                    .unwrap_or(false),
-                                Hover::This => {
-                                    if let SpriteSize::Empty = member.sprite.size {
-                                        // TODO: For other sources as well? Unoptimiso
-                                        let mut width =
-                                            game.assets.font.text_width(&member.text.contents);
-                                        let height = game.assets.font.char_height;
-                                        if let Some(subgame) = subgame {
-                                            for i in 0..serial::CHORE_COUNT {
-                                                if member.text.contents
-                                                    == format!("{{Chore {}}}", i + 1)
-                                                {
-                                                    let question = &subgame.members
-                                                        [editor.selected_index]
-                                                        .todo_list[i]
-                                                        .questions[0];
-                                                    let mut fancy_text =
-                                                        fancy_question_text(question);
-                                                    fancy_text.push("...".plain());
-
-                                                    let font = &game.assets.font;
-
-                                                    width =
-                                                        drawer::fancy_text_width(&fancy_text, font);
-                                                }
-                                            }
-                                        }
-
-                                        let rect = pixels::Rect::from_centre(
-                                            member.position.into(),
-                                            pixels::Size::new(width, height),
-                                        );
-                                        rect.contains_point(mouse.position)
-                                    } else {
-                                        is_position_in_member_image(
-                                            mouse.position,
-                                            member,
-                                            &game.assets.image,
-                                        )
-                                    }
-                                }
-                                Hover::Anywhere => true,
-                            };
-
-                            let mouse_button = match which {
-                                WhichButton::Left => mouse.left_button,
-                                WhichButton::Middle => mouse.middle_button,
-                                WhichButton::Right => {
-                                    mouse.right_button
-                                    //unreachable!();
-                                }
-                            };
-                            constraint
-                                && match state {
-                                    Some(Button::Press) => mouse_button.is_pressed(),
-                                    Some(Button::Down) => mouse_button.is_down(),
-                                    Some(Button::Up) => mouse_button.is_up(),
-                                    Some(Button::Release) => mouse_button.is_released(),
-                                    None => true,
-                                }
-                        }
-                        Question::IsSwitchSetTo { name, switch } => {
-                            let member_switch = get_member(&game.members, name).switch;
-                            if *switch == Switch::Off {
-                                member_switch == Switch::SwitchedOff || member_switch == Switch::Off
-                            } else if *switch == Switch::On {
-                                member_switch == Switch::SwitchedOn || member_switch == Switch::On
-                            } else {
-                                member_switch == *switch
-                            }
-                        }
-                        Question::IsWinStatusSetTo(win_status) => match win_status {
-                            WinStatus::Won => {
-                                matches!(game.win_status, WinStatus::Won | WinStatus::JustWon)
-                            }
-                            WinStatus::Lost => {
-                                matches!(game.win_status, WinStatus::Lost | WinStatus::JustLost)
-                            }
-                            WinStatus::NotYetLost => matches!(
-                                game.win_status,
-                                WinStatus::NotYetLost
-                                    | WinStatus::NotYetWon
-                                    | WinStatus::JustWon
-                                    | WinStatus::Won
-                            ),
-                            WinStatus::NotYetWon => matches!(
-                                game.win_status,
-                                WinStatus::NotYetWon
-                                    | WinStatus::NotYetLost
-                                    | WinStatus::JustLost
-                                    | WinStatus::Lost
-                            ),
-                            _ => game.win_status == *win_status,
-                        },
-                        Question::IsSpriteSetTo(sprite) => member.sprite == *sprite,
-                        Question::IsAnimationFinished => member.animation == Animation::Finished,
-                        Question::IsCollidingWith(CollisionWith::Area(area)) => {
-                            // TODO: Optimise
-                            let mut was_collision = false;
-                            if let SpriteSize::Empty = member.sprite.size {
-                                for x in area.min.x..area.max.x {
-                                    for y in area.min.y..area.max.y {
-                                        let position = pixels::Position::new(x, y);
-                                        if is_position_in_text(
-                                            position,
-                                            member.position,
-                                            &game.assets.font,
-                                            &member.text.contents,
-                                        ) {
-                                            was_collision = true;
-                                            break;
-                                        }
-                                    }
-                                }
-                            } else {
-                                for x in area.min.x..area.max.x {
-                                    for y in area.min.y..area.max.y {
-                                        let position = pixels::Position::new(x, y);
-                                        if is_position_in_member_image(
-                                            position,
-                                            member,
-                                            &game.assets.image,
-                                        ) {
-                                            was_collision = true;
-                                            break;
-                                        }
-                                    }
-                                }
-                            }
-                            was_collision
-                        }
-                        Question::IsCollidingWith(CollisionWith::Member { name }) => {
-                            let maybe = game.members.iter().find(|member| member.name == *name);
-                            let other = maybe.unwrap();
+    if game.error.is_some() {
+        return Ok((events_to_apply, menu_actions));
+    }
 
-                            do_members_collide(member, other, &game.assets.image, &game.assets.font)
-                        }
-                        Question::IsTextSetTo { value } => member.text.contents == *value,
-                        Question::IsVariableSetTo { name, value } => {
-                            environment.context.get(name) == Some(value)
+    let broad_phase = SpatialHash::new(
+        BROAD_PHASE_CELL_SIZE,
+        game.members
+            .iter()
+            .map(|member| member_bounds(member, &game.assets))
+            .collect(),
+    );
+
+    // Waiting chores carry on before any new chores run
+    let mut resumed: Vec<Vec<(usize, Demand)>> = game
+        .members
+        .iter_mut()
+        .map(Member::resume_sequences)
+        .collect();
+    // Only added after the demands run so cancelling doesn't drop chores that just started
+    let mut started_sequences = Vec::new();
+
+    let mut actions: Vec<Vec<(usize, Demand)>> = Vec::new();
+    for (member_index, member) in game.members.iter().enumerate() {
+        let mut requested_demands: Vec<(usize, Demand)> =
+            std::mem::take(&mut resumed[member_index]);
+
+        let mut is_simple_question_true = |question: &Question,
+                                           question_id: QuestionId,
+                                           leaf: usize|
+         -> Result<bool, GameError> {
+            let question_error = |e: Box<dyn Error + Send + Sync>| GameError::Question {
+                id: question_id,
+                message: e.to_string(),
+            };
+            let number = |name: &str| game.numbers.get(name).copied().unwrap_or_default();
+            Ok(match question {
+                Question::None => true,
+                Question::IsTimeAt(When::Start) => game.frame_number == 0,
+                Question::IsTimeAt(When::End) => match game.length {
+                    // TODO: Constants, maybe sub 1?
+                    Length::Short => game.frame_number == 240,
+                    Length::Long => game.frame_number == 480,
+                    Length::Infinite => false,
+                },
+                Question::IsTimeAt(When::Exact { time }) => game.frame_number == *time * 5,
+                Question::IsTimeAt(When::Random { start, end }) => {
+                    if game.triggered_questions.contains(&(question_id, leaf)) {
+                        false
+                    } else {
+                        let frame_number = game
+                            .rng
+                            .number_in_range((*start).max(game.frame_number), *end);
+                        let triggered = game.frame_number == frame_number;
+                        if triggered {
+                            game.triggered_questions.insert((question_id, leaf));
                         }
-                        Question::IsPagedVariableSelected { name, value } => {
-                            let var_index =
-                                |value: &str, offset| value.parse().unwrap_or(1) + offset;
-                            if name == "Paint" {
-                                let max_per_page = max_var_per_page(&game.members, "Paint");
-
-                                let len = padded_len(draw_tool.paint_choices.len(), max_per_page);
-                                let offset = offset_for_page(editor.page, max_per_page, len);
-                                let index = var_index(value, offset);
-                                index == environment.context["Paint Index"].parse().unwrap_or(1)
-                            } else if let Some(subgame) = subgame {
-                                // TODO: Match case?
-                                if name == "Member" {
-                                    let max_per_page =
-                                        max_var_per_page(&game.members, "Member Preview");
-
-                                    let len = padded_len(subgame.members.len(), max_per_page);
-                                    let offset = offset_for_page(editor.page, max_per_page, len);
-                                    let index = var_index(value, offset);
-                                    index
-                                        == environment.context["Member Index"].parse().unwrap_or(1)
-                                } else if name == "Image File" {
-                                    let max_per_page =
-                                        max_var_per_page(&game.members, "Image File");
-                                    let len = padded_len(editor.choices.images.len(), max_per_page);
-                                    let offset = offset_for_page(editor.page, max_per_page, len);
-                                    let index = var_index(value, offset);
-                                    index
-                                        == environment.context["Image File Index"]
-                                            .parse()
-                                            .unwrap_or(1)
-                                } else if name == "Music File" {
-                                    // TODO: Name or File? Is this always consistent?
-                                    let max_per_page =
-                                        max_var_per_page(&game.members, "Music Name");
-                                    let len = padded_len(editor.choices.music.len(), max_per_page);
-                                    let offset = offset_for_page(editor.page, max_per_page, len);
-                                    let index = var_index(value, offset);
-                                    index
-                                        == environment.context["Music File Index"]
-                                            .parse()
-                                            .unwrap_or(1)
-                                } else if name == "Game File" {
-                                    let max_per_page = max_var_per_page(&game.members, "Game Name");
-                                    let len = padded_len(editor.choices.games.len(), max_per_page);
-                                    let offset = offset_for_page(editor.page, max_per_page, len);
-                                    let index = var_index(value, offset);
-                                    index
-                                        == environment.context["Game File Index"]
-                                            .parse()
-                                            .unwrap_or(1)
-                                } else {
+                        triggered
+                    }
+                }
+                Question::IsTimeAt(When::Every { interval, offset }) => {
+                    *interval != 0
+                        && game.frame_number >= *offset
+                        && (game.frame_number - offset).is_multiple_of(*interval)
+                }
+                Question::IsMouseInteracting {
+                    which,
+                    state,
+                    hover,
+                } => {
+                    let constraint = match hover {
+                        Hover::TopMember => in_drawing_order(&game.members)
+                            .into_iter()
+                            .rev()
+                            .find(|other_member| {
+                                if other_member.hidden {
                                     false
+                                } else if let SpriteSize::Empty = other_member.sprite.size {
+                                    is_position_in_text(
+                                        mouse.position,
+                                        other_member.position,
+                                        &game.assets.font,
+                                        &other_member.text.contents,
+                                    )
+                                } else {
+                                    is_position_in_member_image(
+                                        mouse.position,
+                                        other_member,
+                                        &game.assets,
+                                    )
                                 }
-                            } else {
+                            })
+                            .map(|top| std::ptr::eq(member, top))
+                            .unwrap_or(false),
+                        Hover::This => {
+                            if member.hidden {
                                 false
-                            }
-                        }
-                        Question::IsPagedVariableValid { name, value } => {
-                            let var_index_sub_1 =
-                                |value: &str, offset| value.parse().unwrap_or(1) + offset - 1;
-                            // TODO: Different (better?) way of doing things than above?
-                            if name == "Member" {
+                            } else if let SpriteSize::Empty = member.sprite.size {
+                                // TODO: For other sources as well? Unoptimiso
+                                let mut width = game.assets.font.text_width(&member.text.contents);
+                                let height = game.assets.font.char_height;
                                 if let Some(subgame) = subgame {
-                                    let max_per_page =
-                                        max_var_per_page(&game.members, "Member Preview");
-                                    let len = padded_len(subgame.members.len(), max_per_page);
-                                    let offset = offset_for_page(editor.page, max_per_page, len);
-                                    let index = var_index_sub_1(value, offset);
-                                    index < subgame.members.len()
-                                } else {
-                                    false
+                                    if member.text.contents.starts_with("{Chore ") {
+                                        let fancy_text = fancy_question_text_for_chore(
+                                            member,
+                                            &game.members,
+                                            subgame,
+                                            editor,
+                                        );
+
+                                        let font = &game.assets.font;
+
+                                        width = drawer::fancy_text_width(&fancy_text, font);
+                                    }
                                 }
-                            } else if name == "Image File" {
-                                let max_per_page = max_var_per_page(&game.members, "Image File");
-                                let len = padded_len(editor.choices.images.len(), max_per_page);
-                                let offset = offset_for_page(editor.page, max_per_page, len);
-                                let index = var_index_sub_1(value, offset);
-                                index < editor.choices.images.len()
-                            } else if name == "Music File" {
-                                let max_per_page = max_var_per_page(&game.members, "Music Name");
-                                let len = padded_len(editor.choices.music.len(), max_per_page);
-                                let offset = offset_for_page(editor.page, max_per_page, len);
-                                let index = var_index_sub_1(value, offset);
-                                index < editor.choices.music.len()
-                            } else if name == "Game File" {
-                                let max_per_page = max_var_per_page(&game.members, "Game Name");
-                                let len = padded_len(editor.choices.games.len(), max_per_page);
-                                let offset = offset_for_page(editor.page, max_per_page, len);
-                                let index = var_index_sub_1(value, offset);
-                                index < editor.choices.games.len()
-                            } else if name == "Paint" {
-                                let max_per_page = max_var_per_page(&game.members, "Paint");
-                                let len = padded_len(draw_tool.paint_choices.len(), max_per_page);
-                                let offset = offset_for_page(editor.page, max_per_page, len);
-                                let index = var_index_sub_1(value, offset);
-                                index < draw_tool.paint_choices.len()
+
+                                let rect = pixels::Rect::from_centre(
+                                    member.position.into(),
+                                    pixels::Size::new(width, height),
+                                );
+                                rect.contains_point(mouse.position)
                             } else {
-                                false
+                                is_position_in_member_image(mouse.position, member, &game.assets)
                             }
                         }
-                        Question::IsAnimationSpriteValid { index } => {
-                            *index <= editor.animation.len()
-                        }
-                        Question::IsSubgamePlaying => {
-                            // TODO: Current way of checking this
-                            editor.inner_copy.is_some()
-                            //subgame.is_some()
-                        }
-                        Question::IsSubgameEnding => {
-                            //subgame.frame_number ==
-                            // TODO: Rnadom number for now
-                            game.frame_number == 150
+                        Hover::Anywhere => true,
+                    };
+
+                    let mouse_button = match which {
+                        WhichButton::Left => mouse.left_button,
+                        WhichButton::Middle => mouse.middle_button,
+                        WhichButton::Right => {
+                            mouse.right_button
+                            //unreachable!();
                         }
-                        Question::IsShortcutUsed(shortcut) => shortcuts.contains(shortcut),
-                        Question::IsOnWeb => {
-                            #[cfg(target_arch = "wasm32")]
-                            let res = true;
-                            #[cfg(not(target_arch = "wasm32"))]
-                            let res = false;
-                            res
+                    };
+                    constraint && mouse_button.is_in_state(*state)
+                }
+                Question::IsKeyInteracting { key, state } => {
+                    key_button(keyboard, *key).is_in_state(*state)
+                }
+                Question::IsControlInteracting { control, state } => {
+                    control_button(keyboard, *control).is_in_state(*state)
+                }
+                Question::IsSwitchSetTo { name, switch } => {
+                    let member_switch = get_member(&game.members, name)
+                        .map_err(question_error)?
+                        .switch;
+                    is_switch_set_to(member_switch, *switch)
+                }
+                Question::IsTaggedSwitchSetTo { tag, group, switch } => does_group_match(
+                    *group,
+                    game.members.iter().filter(|other| other.has_tag(tag)),
+                    |other| is_switch_set_to(other.switch, *switch),
+                ),
+                Question::IsWinStatusSetTo(win_status) => match win_status {
+                    WinStatus::Won => {
+                        matches!(game.win_status, WinStatus::Won | WinStatus::JustWon)
+                    }
+                    WinStatus::Lost => {
+                        matches!(game.win_status, WinStatus::Lost | WinStatus::JustLost)
+                    }
+                    WinStatus::NotYetLost => matches!(
+                        game.win_status,
+                        WinStatus::NotYetLost
+                            | WinStatus::NotYetWon
+                            | WinStatus::JustWon
+                            | WinStatus::Won
+                    ),
+                    WinStatus::NotYetWon => matches!(
+                        game.win_status,
+                        WinStatus::NotYetWon
+                            | WinStatus::NotYetLost
+                            | WinStatus::JustLost
+                            | WinStatus::Lost
+                    ),
+                    _ => game.win_status == *win_status,
+                },
+                Question::IsSpriteSetTo(sprite) => member.sprite == *sprite,
+                Question::IsVisible => !member.hidden,
+                Question::IsInState { state } => member.is_in_state(state),
+                Question::JustEnteredState { state } => {
+                    member.just_entered_state && member.is_in_state(state)
+                }
+                Question::HasReceived { message } => member.inbox.contains(message),
+                Question::IsAnimationFinished => member.animation == Animation::Finished,
+                Question::IsPathFinished => matches!(
+                    &member.motion,
+                    ActiveMotion::FollowPath { points, next, .. } if *next >= points.len()
+                ),
+                Question::HasTimerPassed { name, frames } => member.has_timer_passed(name, *frames),
+                Question::IsCollidingWith(CollisionWith::Area(area)) => {
+                    if let SpriteSize::Empty = member.sprite.size {
+                        text_rect(member, &game.assets.font).collides(*area)
+                    } else {
+                        let mask = game.assets.mask(member.sprite, member.transform);
+                        collision_object(member, &mask).collides_with_rect(*area)
+                    }
+                }
+                // Also true for instances spawned from it, even once it's been destroyed
+                Question::IsCollidingWith(CollisionWith::Member { name }) => {
+                    get_template(&game.members, &game.templates, name).map_err(question_error)?;
+
+                    broad_phase
+                        .overlapping(member_index)
+                        .into_iter()
+                        .map(|other_index| &game.members[other_index])
+                        .any(|other| {
+                            other.is_called(name) && do_members_collide(member, other, &game.assets)
+                        })
+                }
+                Question::IsCollidingWith(CollisionWith::Tagged { tag, group }) => {
+                    // Members that aren't nearby can't be colliding
+                    let overlapping = broad_phase.overlapping(member_index);
+                    does_group_match(
+                        *group,
+                        game.members
+                            .iter()
+                            .enumerate()
+                            .filter(|(other_index, other)| {
+                                *other_index != member_index && other.has_tag(tag)
+                            }),
+                        |(other_index, other)| {
+                            overlapping.binary_search(&other_index).is_ok()
+                                && do_members_collide(member, other, &game.assets)
+                        },
+                    )
+                }
+                Question::IsTextSetTo { value } => member.text.contents == *value,
+                Question::IsVariableSetTo { name, value } => {
+                    environment.context.get(name) == Some(value)
+                }
+                Question::IsNumberEqualTo { name, value } => number(name) == *value,
+                Question::IsNumberLessThan { name, value } => number(name) < *value,
+                Question::IsNumberGreaterThan { name, value } => number(name) > *value,
+                Question::IsNumberBetween { name, min, max } => {
+                    (*min..=*max).contains(&number(name))
+                }
+                Question::IsPagedVariableSelected { name, value } => {
+                    let var_index = |value: &str, offset| value.parse().unwrap_or(1) + offset;
+                    if name == "Paint" {
+                        let max_per_page = max_var_per_page(&game.members, "Paint");
+
+                        let len = padded_len(draw_tool.paint_choices.len(), max_per_page);
+                        let offset = offset_for_page(editor.page, max_per_page, len);
+                        let index = var_index(value, offset);
+                        index == environment.context["Paint Index"].parse().unwrap_or(1)
+                    } else if let Some(subgame) = subgame {
+                        // TODO: Match case?
+                        if name == "Member" {
+                            let max_per_page = max_var_per_page(&game.members, "Member Preview");
+
+                            let len = padded_len(subgame.members.len(), max_per_page);
+                            let offset = offset_for_page(editor.page, max_per_page, len);
+                            let index = var_index(value, offset);
+                            index == environment.context["Member Index"].parse().unwrap_or(1)
+                        } else if name == "Image File" {
+                            let max_per_page = max_var_per_page(&game.members, "Image File");
+                            let len = padded_len(editor.choices.images.len(), max_per_page);
+                            let offset = offset_for_page(editor.page, max_per_page, len);
+                            let index = var_index(value, offset);
+                            index == environment.context["Image File Index"].parse().unwrap_or(1)
+                        } else if name == "Music File" {
+                            // TODO: Name or File? Is this always consistent?
+                            let max_per_page = max_var_per_page(&game.members, "Music Name");
+                            let len = padded_len(editor.choices.music.len(), max_per_page);
+                            let offset = offset_for_page(editor.page, max_per_page, len);
+                            let index = var_index(value, offset);
+                            index == environment.context["Music File Index"].parse().unwrap_or(1)
+                        } else if name == "Game File" {
+                            let max_per_page = max_var_per_page(&game.members, "Game Name");
+                            let len = padded_len(editor.choices.games.len(), max_per_page);
+                            let offset = offset_for_page(editor.page, max_per_page, len);
+                            let index = var_index(value, offset);
+                            index == environment.context["Game File Index"].parse().unwrap_or(1)
+                        } else if let Some(len) =
+                            growable_len(name, subgame, editor, &environment.context)
+                        {
+                            let slot = value.parse().unwrap_or(1);
+                            let index = growable_index(&game.members, name, editor.page, len, slot);
+                            index
+                                == index_from_context(
+                                    &environment.context,
+                                    &format!("{} Index", name),
+                                )
+                        } else {
+                            false
                         }
-                        Question::IsOnDesktop => {
-                            #[cfg(target_arch = "wasm32")]
-                            let res = false;
-                            #[cfg(not(target_arch = "wasm32"))]
-                            let res = true;
-                            res
+                    } else {
+                        false
+                    }
+                }
+                Question::IsPagedVariableValid { name, value } => {
+                    let var_index_sub_1 =
+                        |value: &str, offset| value.parse().unwrap_or(1) + offset - 1;
+                    // TODO: Different (better?) way of doing things than above?
+                    if name == "Member" {
+                        if let Some(subgame) = subgame {
+                            let max_per_page = max_var_per_page(&game.members, "Member Preview");
+                            let len = padded_len(subgame.members.len(), max_per_page);
+                            let offset = offset_for_page(editor.page, max_per_page, len);
+                            let index = var_index_sub_1(value, offset);
+                            index < subgame.members.len()
+                        } else {
+                            false
                         }
-                    };
-            }
-            if triggered {
-                for demand in &chore.demands {
-                    if let Demand::None = demand {
+                    } else if name == "Image File" {
+                        let max_per_page = max_var_per_page(&game.members, "Image File");
+                        let len = padded_len(editor.choices.images.len(), max_per_page);
+                        let offset = offset_for_page(editor.page, max_per_page, len);
+                        let index = var_index_sub_1(value, offset);
+                        index < editor.choices.images.len()
+                    } else if name == "Music File" {
+                        let max_per_page = max_var_per_page(&game.members, "Music Name");
+                        let len = padded_len(editor.choices.music.len(), max_per_page);
+                        let offset = offset_for_page(editor.page, max_per_page, len);
+                        let index = var_index_sub_1(value, offset);
+                        index < editor.choices.music.len()
+                    } else if name == "Game File" {
+                        let max_per_page = max_var_per_page(&game.members, "Game Name");
+                        let len = padded_len(editor.choices.games.len(), max_per_page);
+                        let offset = offset_for_page(editor.page, max_per_page, len);
+                        let index = var_index_sub_1(value, offset);
+                        index < editor.choices.games.len()
+                    } else if name == "Paint" {
+                        let max_per_page = max_var_per_page(&game.members, "Paint");
+                        let len = padded_len(draw_tool.paint_choices.len(), max_per_page);
+                        let offset = offset_for_page(editor.page, max_per_page, len);
+                        let index = var_index_sub_1(value, offset);
+                        index < draw_tool.paint_choices.len()
+                    } else if let Some(len) = subgame.as_ref().and_then(|subgame| {
+                        growable_len(name, subgame, editor, &environment.context)
+                    }) {
+                        let slot = value.parse().unwrap_or(1);
+                        let index = growable_index(&game.members, name, editor.page, len, slot);
+                        index <= len
                     } else {
-                        requested_demands.push(demand.clone());
+                        false
                     }
                 }
+                Question::IsAnimationSpriteValid { index } => *index <= editor.animation.len(),
+                Question::IsSubgamePlaying => {
+                    // TODO: Current way of checking this
+                    editor.inner_copy.is_some()
+                    //subgame.is_some()
+                }
+                Question::IsSubgameEnding => {
+                    //subgame.frame_number ==
+                    // TODO: Rnadom number for now
+                    game.frame_number == 150
+                }
+                Question::IsShortcutUsed(shortcut) => shortcuts.contains(shortcut),
+                Question::IsDifficulty(level) => environment.difficulty_level == *level,
+                Question::IsOnWeb => {
+                    #[cfg(target_arch = "wasm32")]
+                    let res = true;
+                    #[cfg(not(target_arch = "wasm32"))]
+                    let res = false;
+                    res
+                }
+                Question::IsOnDesktop => {
+                    #[cfg(target_arch = "wasm32")]
+                    let res = false;
+                    #[cfg(not(target_arch = "wasm32"))]
+                    let res = true;
+                    res
+                }
+                Question::Not(_) | Question::AnyOf(_) | Question::AllOf(_) => {
+                    unreachable!("Grouped questions are evaluated by is_question_true")
+                }
+            })
+        };
+
+        for (chore_index, chore) in member.todo_list.iter().enumerate() {
+            if chore
+                .state
+                .as_ref()
+                .is_some_and(|state| !member.is_in_state(state))
+            {
+                continue;
+            }
+            // A chore that's still waiting has to finish before it can start again
+            if member.is_waiting_on(chore_index) {
+                continue;
+            }
+            let mut triggered = true;
+            for (question_index, question) in chore.questions.iter().enumerate() {
+                let question_id = QuestionId::new(member_index, chore_index, question_index);
+                triggered = triggered
+                    && is_question_true(question, question_id, 0, &mut is_simple_question_true)?;
+            }
+            if triggered {
+                if let Some(sequence) =
+                    run_until_wait(chore_index, &chore.demands, &mut requested_demands)
+                {
+                    started_sequences.push((member_index, sequence));
+                }
             }
         }
         actions.push(requested_demands);
@@ -846,8 +1475,27 @@ pub fn update_game(
         w => w,
     };
 
+    // Applied once every demand has run so member indices stay put until then
+    let mut spawned = Vec::new();
+    let mut destroyed = Vec::new();
+    let mut sent = Vec::new();
+
+    // Every question has been asked, so entering a state last frame no longer counts
+    for member in game.members.iter_mut() {
+        member.just_entered_state = false;
+    }
+
     for (i, action) in actions.into_iter().enumerate() {
-        for demand in action {
+        for (chore_index, demand) in action {
+            let demand = if game.scales_with_difficulty {
+                scaled_for_difficulty(demand, environment.difficulty_level)
+            } else {
+                demand
+            };
+            let demand_error = |message: &str| GameError::Demand {
+                id: ChoreId::new(i, chore_index),
+                message: message.to_owned(),
+            };
             match demand {
                 Demand::None => {}
                 // Game Stuff
@@ -855,12 +1503,27 @@ pub fn update_game(
                     game.members[i].sprite = sprite;
                     game.members[i].animation = Animation::None;
                 }
+                Demand::SetFlip { x, y } => {
+                    game.members[i].transform.flip_x = x;
+                    game.members[i].transform.flip_y = y;
+                }
+                Demand::SetRotation(rotation) => game.members[i].transform.rotation = rotation,
+                Demand::SetScale { scale } => game.members[i].transform.scale = scale.max(1),
+                Demand::SetTint(tint) => {
+                    game.members[i].tint = Some(Colour::new(tint.r, tint.g, tint.b, tint.a))
+                }
+                Demand::ClearTint => game.members[i].tint = None,
                 Demand::SetSwitch(switch) => {
-                    // TODO: Constrain switch to On/Off
-                    game.members[i].applied_switch = match switch {
-                        Switch::On | Switch::SwitchedOn => Switch::SwitchedOn,
-                        Switch::Off | Switch::SwitchedOff => Switch::SwitchedOff,
-                    };
+                    game.members[i].applied_switch = applied_switch(switch);
+                }
+                Demand::SetTaggedSwitch { tag, switch } => {
+                    for member in game
+                        .members
+                        .iter_mut()
+                        .filter(|member| member.has_tag(&tag))
+                    {
+                        member.applied_switch = applied_switch(switch);
+                    }
                 }
                 Demand::SetText(text) => {
                     game.members[i].text = Text {
@@ -894,12 +1557,116 @@ pub fn update_game(
                     speed,
                     sprites,
                 } => {
-                    game.members[i].sprite = sprites[0];
+                    game.members[i].sprite = *sprites
+                        .first()
+                        .ok_or_else(|| demand_error("Animate has no sprites"))?;
                     game.members[i].animation = Animation::started(sprites.clone(), speed, style);
                 }
                 Demand::StopAnimation => {
                     game.members[i].animation = Animation::None;
                 }
+                Demand::StartTimer { name } => {
+                    game.members[i].timers.insert(name, 0);
+                }
+                Demand::StopTimer { name } => {
+                    game.members[i].timers.remove(&name);
+                }
+                Demand::Wait { .. } => {
+                    unreachable!("Waits are split into sequences before demands run")
+                }
+                Demand::CancelSequence => game.members[i].sequences.clear(),
+                Demand::EnterState { state } => game.members[i].enter_state(state),
+                // Messages to members that don't exist, maybe destroyed, are dropped
+                Demand::Send { message, to } => sent.push((message, to)),
+                Demand::Spawn { template, at } => {
+                    let original = get_template(&game.members, &game.templates, &template)
+                        .map_err(|e| demand_error(&e.to_string()))?;
+                    let template = original.template.clone().unwrap_or(template);
+                    game.spawn_count += 1;
+                    let mut instance = Member {
+                        name: format!("{} #{}", template, game.spawn_count),
+                        position: game.members[i].position,
+                        sprite: original.sprite,
+                        text: original.text.clone(),
+                        todo_list: original.todo_list.clone(),
+                        tags: original.tags.clone(),
+                        solid: original.solid,
+                        layer: original.layer,
+                        hidden: original.hidden,
+                        transform: original.transform,
+                        tint: original.tint,
+                        state: original.state.clone(),
+                        template: Some(template),
+                        ..Default::default()
+                    };
+                    instance.position = jump_position(
+                        &at,
+                        &instance,
+                        &game.members,
+                        &game.assets.image,
+                        &mut game.rng,
+                        mouse,
+                    );
+                    spawned.push(instance);
+                }
+                Demand::Destroy => {
+                    if !destroyed.contains(&i) {
+                        destroyed.push(i);
+                    }
+                }
+                Demand::EmitParticles {
+                    style,
+                    count,
+                    speed,
+                    spread,
+                    lifetime,
+                } => {
+                    let particles = emit_particles(
+                        game.members[i].position,
+                        style,
+                        count,
+                        speed,
+                        spread,
+                        lifetime,
+                        &mut game.rng,
+                    );
+                    game.particles.extend(particles);
+                }
+                Demand::Show => game.members[i].hidden = false,
+                Demand::Hide => game.members[i].hidden = true,
+                // Only moves when it isn't already above or below everything else
+                Demand::BringToFront => {
+                    let others = game.members.iter().enumerate().filter(|(j, _)| *j != i);
+                    if let Some(top) = others.map(|(_, other)| other.layer).max() {
+                        game.members[i].layer = game.members[i].layer.max(top + 1);
+                    }
+                }
+                Demand::SendToBack => {
+                    let others = game.members.iter().enumerate().filter(|(j, _)| *j != i);
+                    if let Some(bottom) = others.map(|(_, other)| other.layer).min() {
+                        game.members[i].layer = game.members[i].layer.min(bottom - 1);
+                    }
+                }
+                Demand::SetLayer { layer } => game.members[i].layer = layer,
+                Demand::CameraFollow {
+                    name,
+                    dead_zone_width,
+                    dead_zone_height,
+                    bounds,
+                } => {
+                    game.camera.aim = CameraAim::Follow {
+                        name,
+                        dead_zone: pixels::Size::new(dead_zone_width, dead_zone_height),
+                        bounds,
+                    };
+                }
+                Demand::CameraPanTo { point, speed } => {
+                    let speed_constant = 2.0;
+                    game.camera.aim = CameraAim::PanTo {
+                        point: Vec2::new(point.x as f32, point.y as f32),
+                        speed: speed_constant * speed.multiplier(),
+                    };
+                }
                 Demand::Motion(Motion::Stop) => {
                     // TODO: Queueing motions?
                     game.members[i].motion = ActiveMotion::Stop;
@@ -909,12 +1676,27 @@ pub fn update_game(
                     let directions: Vec<serial::Direction> =
                         direction.clone().into_iter().collect();
                     // TODO: If empty then choose from 8 possible directions
-                    let direction = *directions.choose(&mut game.rng).unwrap();
+                    let direction = *directions
+                        .choose(&mut game.rng)
+                        .ok_or_else(|| demand_error("Go has no direction"))?;
                     game.members[i].motion = ActiveMotion::Go { direction, speed }
                 }
                 Demand::Motion(Motion::GoToPoint { point, speed }) => {
                     game.members[i].motion = ActiveMotion::GoToPoint { point, speed }
                 }
+                Demand::Motion(Motion::FollowPath {
+                    points,
+                    speed,
+                    looping,
+                }) => {
+                    game.members[i].motion = ActiveMotion::FollowPath {
+                        points,
+                        speed,
+                        looping,
+                        next: 0,
+                        forwards: true,
+                    }
+                }
                 Demand::Motion(Motion::Swap { name }) => {
                     let maybe_index = game
                         .members
@@ -961,45 +1743,15 @@ pub fn update_game(
                         },
                     };
                 }
-                Demand::Motion(Motion::JumpTo(JumpLocation::Point(position))) => {
-                    game.members[i].position = position.into();
-                }
-                Demand::Motion(Motion::JumpTo(JumpLocation::Area(area))) => {
-                    // TODO: Handle size of object, (incl bg sizes? what does this mean)
-                    log::debug!("AREA BEFORE: {:?}", area);
-                    let area = constrained_area(&game.assets.image, &game.members[i], area);
-                    log::debug!("AREA AFTER: {:?}", area);
-                    if area.w > 0.0 {
-                        game.members[i].position.x =
-                            game.rng.number_in_range(area.x, area.x + area.w);
-                    } else {
-                        // TODO: ?
-                        game.members[i].position.x = area.x;
-                    }
-                    if area.h > 0.0 {
-                        game.members[i].position.y =
-                            game.rng.number_in_range(area.y, area.y + area.h);
-                    } else {
-                        // TODO: ?
-                        game.members[i].position.y = area.y;
-                    }
-                }
-                Demand::Motion(Motion::JumpTo(JumpLocation::Mouse)) => {
-                    game.members[i].position = mouse.position.into();
-                }
-                Demand::Motion(Motion::JumpTo(JumpLocation::Member { name })) => {
-                    let maybe = game
-                        .members
-                        .iter()
-                        .find(|member| member.name == *name)
-                        .map(|other| other.position);
-                    if let Some(other) = maybe {
-                        game.members[i].position = other;
-                    }
-                }
-                Demand::Motion(Motion::JumpTo(JumpLocation::Relative { offset })) => {
-                    let offset: Vec2 = offset.into();
-                    game.members[i].position += offset;
+                Demand::Motion(Motion::JumpTo(location)) => {
+                    game.members[i].position = jump_position(
+                        &location,
+                        &game.members[i],
+                        &game.members,
+                        &game.assets.image,
+                        &mut game.rng,
+                        mouse,
+                    );
                 }
                 Demand::Motion(Motion::ClampPosition { area }) => {
                     let constrained_area =
@@ -1027,6 +1779,57 @@ pub fn update_game(
                         speed,
                     };
                 }
+                Demand::Motion(Motion::TargetTagged { tag, offset, speed }) => {
+                    let position = game.members[i].position;
+                    let closest = game
+                        .members
+                        .iter()
+                        .enumerate()
+                        .filter(|(index, member)| *index != i && member.has_tag(&tag))
+                        .map(|(_, member)| member)
+                        .min_by(|a, b| {
+                            a.position
+                                .distance_squared(position)
+                                .total_cmp(&b.position.distance_squared(position))
+                        });
+                    if let Some(closest) = closest {
+                        game.members[i].motion = ActiveMotion::Target {
+                            name: closest.name.clone(),
+                            offset: offset.into(),
+                            speed,
+                        };
+                    }
+                }
+                Demand::Motion(Motion::Physics {
+                    velocity_x,
+                    velocity_y,
+                    gravity,
+                    drag,
+                    bounciness,
+                    bounds,
+                }) => {
+                    game.members[i].motion = ActiveMotion::Physics {
+                        velocity: Vec2::new(velocity_x, velocity_y),
+                        gravity,
+                        drag,
+                        bounciness,
+                        bounds,
+                    };
+                }
+                Demand::Motion(Motion::Impulse { x, y }) => {
+                    let impulse = Vec2::new(x, y);
+                    if let ActiveMotion::Physics { velocity, .. } = &mut game.members[i].motion {
+                        *velocity += impulse;
+                    } else {
+                        game.members[i].motion = ActiveMotion::Physics {
+                            velocity: impulse,
+                            gravity: 0.0,
+                            drag: 0.0,
+                            bounciness: 0.0,
+                            bounds: None,
+                        };
+                    }
+                }
                 Demand::Motion(Motion::AttachFromPositions { name }) => {
                     // TODO: Don't need index
                     let maybe_index = game
@@ -1053,6 +1856,33 @@ pub fn update_game(
                 Demand::StopSounds => {
                     *sounds_to_play = SoundQueue::Stopped;
                 }
+                // Numbers
+                Demand::SetNumber { name, value } => {
+                    game.numbers.insert(name, value);
+                }
+                Demand::AddToNumber { name, value } => {
+                    let number = game.numbers.entry(name).or_default();
+                    *number = number.saturating_add(value);
+                }
+                Demand::MultiplyNumber { name, value } => {
+                    let number = game.numbers.entry(name).or_default();
+                    *number = number.saturating_mul(value);
+                }
+                Demand::SetNumberToRandom { name, min, max } => {
+                    // Includes both ends, counted up from min so rounding is the same either side of 0
+                    let span = max.saturating_sub(min).max(0);
+                    let offset: i32 = game.rng.number_in_range(0, span.saturating_add(1));
+                    game.numbers
+                        .insert(name, min.saturating_add(offset.min(span)));
+                }
+                Demand::SetTextFromNumber { name } => {
+                    game.members[i].text.contents = game
+                        .numbers
+                        .get(&name)
+                        .copied()
+                        .unwrap_or_default()
+                        .to_string();
+                }
                 // Editor Stuff
                 Demand::New => {
                     menu_actions.push(menu::Action::New);
@@ -1149,6 +1979,16 @@ pub fn update_game(
                                 environment
                                     .update_var("Game File Name", &editor.choices.games[index - 1]);
                             }
+                        } else if let Some(len) =
+                            growable_len(&name, subgame, editor, &environment.context)
+                        {
+                            let slot = value.parse().unwrap_or(1);
+                            let index =
+                                growable_index(&game.members, &name, editor.page, len, slot);
+                            if index <= len {
+                                environment
+                                    .update_var(format!("{} Index", name), (index + 1).to_string());
+                            }
                         }
                     }
                 }
@@ -1201,8 +2041,11 @@ pub fn update_game(
                     let question_index = question_index_from_context(&environment.context);
 
                     if let Some(subgame) = subgame {
-                        match &subgame.members[editor.selected_index].todo_list[chore_index]
-                            .questions[question_index]
+                        match &chore(
+                            &subgame.members[editor.selected_index].todo_list,
+                            chore_index,
+                        )
+                        .question(question_index)
                         {
                             Question::IsMouseInteracting {
                                 which: _which,
@@ -1260,8 +2103,11 @@ pub fn update_game(
                     let demand_index = demand_index_from_context(&environment.context);
 
                     if let Some(subgame) = subgame {
-                        match &subgame.members[editor.selected_index].todo_list[chore_index].demands
-                            [demand_index]
+                        match &chore(
+                            &subgame.members[editor.selected_index].todo_list,
+                            chore_index,
+                        )
+                        .demand(demand_index)
                         {
                             Demand::SetSwitch(switch) => {
                                 environment.update_var_as_debug("Switch", switch);
@@ -1298,6 +2144,15 @@ pub fn update_game(
                                 environment.update_var("X", point.x.to_string());
                                 environment.update_var("Y", point.y.to_string());
                             }
+                            Demand::Motion(Motion::FollowPath {
+                                points,
+                                speed,
+                                looping,
+                            }) => {
+                                environment.update_var("Path", path_text(points));
+                                environment.update_var("Speed", speed.to_string());
+                                environment.update_var_as_debug("Path Looping", looping);
+                            }
                             Demand::Motion(
                                 Motion::JumpTo(JumpLocation::Area(area))
                                 | Motion::ClampPosition { area },
@@ -1396,7 +2251,9 @@ pub fn update_game(
                     );
                     // TODO: More robust solution
                     let mut valid_numbers: Vec<i32> = Vec::new();
-                    for member in &subgame.unwrap().members {
+                    let subgame =
+                        subgame.ok_or_else(|| demand_error("AddMember needs a game to edit"))?;
+                    for member in &subgame.members {
                         if let Ok(num) = member.name.parse() {
                             valid_numbers.push(num);
                         }
@@ -1412,7 +2269,7 @@ pub fn update_game(
                             switch: Switch::Off,
                             sprite: sprite_from_context(&environment.context),
                             animation: Animation::None,
-                            todo_list: default_todo_list(),
+                            todo_list: Vec::new(),
                             ..Default::default()
                         },
                     })
@@ -1509,6 +2366,68 @@ pub fn update_game(
                         id: QuestionId::new(editor.selected_index, chore_index, question_index),
                     });
                 }
+                Demand::NegateQuestion => {
+                    if let Some(subgame) = subgame {
+                        let chore_index = chore_index_from_context(&environment.context);
+                        let question_index = question_index_from_context(&environment.context);
+
+                        let question = match chore(
+                            &subgame.members[editor.selected_index].todo_list,
+                            chore_index,
+                        )
+                        .question(question_index)
+                        {
+                            Question::None => Question::None,
+                            Question::Not(question) => *question,
+                            question => Question::Not(Box::new(question)),
+                        };
+
+                        if question != Question::None {
+                            events_to_apply.push(Event::UpdateQuestion {
+                                id: QuestionId::new(
+                                    editor.selected_index,
+                                    chore_index,
+                                    question_index,
+                                ),
+                                question,
+                            });
+                        }
+                    }
+                }
+                Demand::GroupQuestionWithNext(group) => {
+                    if let Some(subgame) = subgame {
+                        let chore_index = chore_index_from_context(&environment.context);
+                        let question_index = question_index_from_context(&environment.context);
+
+                        let mut grouped_chore = chore(
+                            &subgame.members[editor.selected_index].todo_list,
+                            chore_index,
+                        );
+                        if grouped_chore.group_question_with_next(question_index, group) {
+                            events_to_apply.push(Event::UpdateChore {
+                                id: ChoreId::new(editor.selected_index, chore_index),
+                                chore: Box::new(grouped_chore),
+                            });
+                        }
+                    }
+                }
+                Demand::UngroupQuestion => {
+                    if let Some(subgame) = subgame {
+                        let chore_index = chore_index_from_context(&environment.context);
+                        let question_index = question_index_from_context(&environment.context);
+
+                        let mut ungrouped_chore = chore(
+                            &subgame.members[editor.selected_index].todo_list,
+                            chore_index,
+                        );
+                        if ungrouped_chore.ungroup_question(question_index) {
+                            events_to_apply.push(Event::UpdateChore {
+                                id: ChoreId::new(editor.selected_index, chore_index),
+                                chore: Box::new(ungrouped_chore),
+                            });
+                        }
+                    }
+                }
                 Demand::MoveDemandUp => {
                     let chore_index = chore_index_from_context(&environment.context);
                     let demand_index = demand_index_from_context(&environment.context);
@@ -1544,8 +2463,72 @@ pub fn update_game(
 
                         events_to_apply.push(Event::SetStartSprite {
                             index: editor.selected_index,
-                            from: original_sprite,
-                            to: new_sprite,
+                            from: original_sprite,
+                            to: new_sprite,
+                        });
+                    }
+                }
+                // An empty label goes back to describing the chore by its questions
+                Demand::SetChoreLabel => {
+                    if let Some(subgame) = subgame {
+                        let chore_index = chore_index_from_context(&environment.context);
+
+                        let mut labelled_chore = chore(
+                            &subgame.members[editor.selected_index].todo_list,
+                            chore_index,
+                        );
+                        labelled_chore.label = environment
+                            .get_var_for_text("Label")
+                            .filter(|label| !label.is_empty());
+
+                        events_to_apply.push(Event::UpdateChore {
+                            id: ChoreId::new(editor.selected_index, chore_index),
+                            chore: Box::new(labelled_chore),
+                        });
+                    }
+                }
+                // An empty state clears it
+                Demand::SetStartState => {
+                    if let Some(subgame) = subgame {
+                        let original_state = subgame.members[editor.selected_index].state.clone();
+
+                        let new_state = environment
+                            .get_var_for_text("State")
+                            .filter(|state| !state.is_empty());
+
+                        events_to_apply.push(Event::SetStartState {
+                            index: editor.selected_index,
+                            from: original_state,
+                            to: new_state,
+                        });
+                    }
+                }
+                Demand::SetChoreState => {
+                    if let Some(subgame) = subgame {
+                        let chore_index = chore_index_from_context(&environment.context);
+
+                        let mut state_chore = chore(
+                            &subgame.members[editor.selected_index].todo_list,
+                            chore_index,
+                        );
+                        state_chore.state = environment
+                            .get_var_for_text("State")
+                            .filter(|state| !state.is_empty());
+
+                        events_to_apply.push(Event::UpdateChore {
+                            id: ChoreId::new(editor.selected_index, chore_index),
+                            chore: Box::new(state_chore),
+                        });
+                    }
+                }
+                Demand::ToggleSolid => {
+                    if let Some(subgame) = subgame {
+                        let solid = subgame.members[editor.selected_index].solid;
+
+                        events_to_apply.push(Event::SetSolid {
+                            index: editor.selected_index,
+                            from: solid,
+                            to: !solid,
                         });
                     }
                 }
@@ -1629,6 +2612,16 @@ pub fn update_game(
                     menu_actions.push(menu::Action::Play);
                     music_maker.actions.insert(music::Action::PlayPhrase);
                 }
+                // The level is read back from the variable every frame
+                Demand::PlayAtDifficulty(level) => {
+                    if editor.difficulty_before_preview.is_none() {
+                        editor.difficulty_before_preview =
+                            environment.get_var_for_text("Difficulty");
+                    }
+                    environment.update_var_as_debug("Difficulty", level);
+                    menu_actions.push(menu::Action::Play);
+                    music_maker.actions.insert(music::Action::PlayPhrase);
+                }
                 Demand::Pause => {
                     menu_actions.push(menu::Action::Pause);
                     music_maker.actions.insert(music::Action::PausePhrase);
@@ -1712,9 +2705,38 @@ pub fn update_game(
         }
     }
 
+    for (i, sequence) in started_sequences {
+        game.members[i].sequences.push(sequence);
+    }
+
+    if !destroyed.is_empty() {
+        destroyed.sort_unstable();
+        for &i in destroyed.iter().rev() {
+            let member = game.members.remove(i);
+            if member.template.is_none() {
+                game.templates.insert(member.name.clone(), member);
+            }
+        }
+        game.triggered_questions = game
+            .triggered_questions
+            .drain()
+            .filter(|(id, _)| !destroyed.contains(&id.member))
+            .map(|(id, leaf)| {
+                let member = id.member - destroyed.iter().filter(|&&i| i < id.member).count();
+                (QuestionId { member, ..id }, leaf)
+            })
+            .collect();
+    }
+    game.members.extend(spawned);
+
+    // Spawned instances can receive messages from the frame they were spawned on
+    deliver_messages(&mut game.members, sent);
+
     for i in 0..game.members.len() {
         // TODO: Handle motion directly after actions? 1 at a time or all at once?
 
+        let start = game.members[i].position;
+
         let move_to = |x: f32, other: f32, velocity: f32| {
             if (x - other).abs() > velocity.abs() {
                 x + velocity
@@ -1725,15 +2747,8 @@ pub fn update_game(
 
         game.members[i].motion = match game.members[i].motion.clone() {
             ActiveMotion::Go { direction, speed } => {
-                let speed_multiplier = match speed {
-                    Speed::VerySlow => 0.25,
-                    Speed::Slow => 0.5,
-                    Speed::Normal => 1.0,
-                    Speed::Fast => 2.0,
-                    Speed::VeryFast => 4.0,
-                };
                 let speed_constant = 2.0;
-                let speed_value = speed_constant * speed_multiplier;
+                let speed_value = speed_constant * speed.multiplier();
                 let movement = match direction {
                     Direction::East => Vec2::new(speed_value, 0.0),
                     Direction::SouthEast => Vec2::new(speed_value, speed_value),
@@ -1748,15 +2763,8 @@ pub fn update_game(
                 ActiveMotion::Go { direction, speed }
             }
             ActiveMotion::GoToPoint { point, speed } => {
-                let speed_multiplier = match speed {
-                    Speed::VerySlow => 0.25,
-                    Speed::Slow => 0.5,
-                    Speed::Normal => 1.0,
-                    Speed::Fast => 2.0,
-                    Speed::VeryFast => 4.0,
-                };
                 let speed_constant = 2.0;
-                let speed_value = speed_constant * speed_multiplier;
+                let speed_value = speed_constant * speed.multiplier();
                 let target_vector = Vec2::from(point) - game.members[i].position;
                 let d = (target_vector.x.powf(2.0) + target_vector.y.powf(2.0)).sqrt();
                 let velocity = Vec2 {
@@ -1769,6 +2777,39 @@ pub fn update_game(
                     move_to(game.members[i].position.y, point.y as f32, velocity.y);
                 ActiveMotion::GoToPoint { point, speed }
             }
+            ActiveMotion::FollowPath {
+                points,
+                speed,
+                looping,
+                mut next,
+                mut forwards,
+            } => {
+                if let Some(&point) = points.get(next) {
+                    let speed_constant = 2.0;
+                    let speed_value = speed_constant * speed.multiplier();
+                    let target_vector = Vec2::from(point) - game.members[i].position;
+                    let d = (target_vector.x.powf(2.0) + target_vector.y.powf(2.0)).sqrt();
+                    let velocity = Vec2 {
+                        x: target_vector.x / d * speed_value,
+                        y: target_vector.y / d * speed_value,
+                    };
+                    game.members[i].position.x =
+                        move_to(game.members[i].position.x, point.x as f32, velocity.x);
+                    game.members[i].position.y =
+                        move_to(game.members[i].position.y, point.y as f32, velocity.y);
+
+                    if game.members[i].position == Vec2::from(point) {
+                        (next, forwards) = next_waypoint(points.len(), next, forwards, looping);
+                    }
+                }
+                ActiveMotion::FollowPath {
+                    points,
+                    speed,
+                    looping,
+                    next,
+                    forwards,
+                }
+            }
             ActiveMotion::Attach { name, offset } => {
                 let maybe = game
                     .members
@@ -1791,15 +2832,8 @@ pub fn update_game(
                     .find(|member| member.name == *name)
                     .map(|other| other.position);
                 if let Some(point) = maybe {
-                    let speed_multiplier = match speed {
-                        Speed::VerySlow => 0.25,
-                        Speed::Slow => 0.5,
-                        Speed::Normal => 1.0,
-                        Speed::Fast => 2.0,
-                        Speed::VeryFast => 4.0,
-                    };
                     let speed_constant = 2.0;
-                    let speed_value = speed_constant * speed_multiplier;
+                    let speed_value = speed_constant * speed.multiplier();
                     let target_vector = point - game.members[i].position;
                     let d = (target_vector.x.powf(2.0) + target_vector.y.powf(2.0)).sqrt();
                     let velocity = Vec2 {
@@ -1822,18 +2856,10 @@ pub fn update_game(
                 speed,
                 handling,
             } => {
-                // TODO: Try not to overlap
-                let speed_multiplier = match speed {
-                    Speed::VerySlow => 0.25,
-                    Speed::Slow => 0.5,
-                    Speed::Normal => 1.0,
-                    Speed::Fast => 2.0,
-                    Speed::VeryFast => 4.0,
-                };
                 let speed_constant = 2.0;
-                let speed_value = speed_constant * speed_multiplier;
-                let x = macroquad::rand::gen_range(-speed_value, speed_value);
-                let y = macroquad::rand::gen_range(-speed_value, speed_value);
+                let speed_value = speed_constant * speed.multiplier();
+                let x = game.rng.number_in_range(-speed_value, speed_value);
+                let y = game.rng.number_in_range(-speed_value, speed_value);
                 let pos = game.members[i].position;
                 // TODO: Handle size of object, incl bg sizes
                 let area2 = constrained_area(&game.assets.image, &game.members[i], area);
@@ -1871,20 +2897,12 @@ pub fn update_game(
                 handling,
                 mut velocity,
             } => {
-                // TODO: Try not to overlap
-                let speed_multiplier = match speed {
-                    Speed::VerySlow => 0.25,
-                    Speed::Slow => 0.5,
-                    Speed::Normal => 1.0,
-                    Speed::Fast => 2.0,
-                    Speed::VeryFast => 4.0,
-                };
                 let speed_constant = 2.0;
-                let speed_value = speed_constant * speed_multiplier;
+                let speed_value = speed_constant * speed.multiplier();
                 if velocity == Vec2::default() || game.rng.number_in_range(0.0, 1.0) < 0.1 {
                     // TODO: generate velocity from possible directions
-                    let x = macroquad::rand::gen_range(-speed_value, speed_value);
-                    let y = macroquad::rand::gen_range(-speed_value, speed_value);
+                    let x = game.rng.number_in_range(-speed_value, speed_value);
+                    let y = game.rng.number_in_range(-speed_value, speed_value);
                     velocity = Vec2::new(x, y);
                 }
                 let pos = game.members[i].position;
@@ -1923,23 +2941,15 @@ pub fn update_game(
                 handling,
                 mut velocity,
             } => {
-                // TODO: Try not to overlap
-                let speed_multiplier = match speed {
-                    Speed::VerySlow => 0.25,
-                    Speed::Slow => 0.5,
-                    Speed::Normal => 1.0,
-                    Speed::Fast => 2.0,
-                    Speed::VeryFast => 4.0,
-                };
                 let speed_constant = 2.0;
-                let speed_value = speed_constant * speed_multiplier;
+                let speed_value = speed_constant * speed.multiplier();
                 let constrained_area = constrained_area(&game.assets.image, &game.members[i], area);
                 let _pos = game.members[i].position;
 
                 if velocity == Vec2::default() {
                     // TODO: generate velocity from possible directions
-                    let x = macroquad::rand::gen_range(-speed_value, speed_value);
-                    let y = macroquad::rand::gen_range(-speed_value, speed_value);
+                    let x = game.rng.number_in_range(-speed_value, speed_value);
+                    let y = game.rng.number_in_range(-speed_value, speed_value);
                     velocity = Vec2::new(x, y);
                     log::debug!("INITIAL: {:?}", velocity);
                     // TODO:
@@ -1993,16 +3003,8 @@ pub fn update_game(
                 handling,
                 mut velocity,
             } => {
-                // TODO: Try not to overlap
-                let speed_multiplier = match speed {
-                    Speed::VerySlow => 0.25,
-                    Speed::Slow => 0.5,
-                    Speed::Normal => 1.0,
-                    Speed::Fast => 2.0,
-                    Speed::VeryFast => 4.0,
-                };
                 let speed_constant = 2.0;
-                let speed_value = speed_constant * speed_multiplier;
+                let speed_value = speed_constant * speed.multiplier();
                 let constrained_area = constrained_area(&game.assets.image, &game.members[i], area);
 
                 let acceleration = speed_value / 15.0;
@@ -2051,10 +3053,64 @@ pub fn update_game(
                     velocity,
                 }
             }
+            ActiveMotion::Physics {
+                mut velocity,
+                gravity,
+                drag,
+                bounciness,
+                bounds,
+            } => {
+                velocity.y += gravity;
+                velocity *= 1.0 - drag.clamp(0.0, 1.0);
+                game.members[i].position += velocity;
+
+                // Edges push back by the bounciness, 0 stops dead and 1 loses nothing
+                if let Some(bounds) = bounds {
+                    let area = constrained_area(&game.assets.image, &game.members[i], bounds);
+                    let position = &mut game.members[i].position;
+                    if position.x < area.x {
+                        position.x = area.x;
+                        velocity.x = velocity.x.abs() * bounciness;
+                    } else if position.x > area.x + area.w {
+                        position.x = area.x + area.w;
+                        velocity.x = -velocity.x.abs() * bounciness;
+                    }
+                    if position.y < area.y {
+                        position.y = area.y;
+                        velocity.y = velocity.y.abs() * bounciness;
+                    } else if position.y > area.y + area.h {
+                        position.y = area.y + area.h;
+                        velocity.y = -velocity.y.abs() * bounciness;
+                    }
+                }
+                ActiveMotion::Physics {
+                    velocity,
+                    gravity,
+                    drag,
+                    bounciness,
+                    bounds,
+                }
+            }
             ActiveMotion::Stop => ActiveMotion::Stop,
         };
+
+        // Attached members go wherever they're attached to
+        if !matches!(
+            game.members[i].motion,
+            ActiveMotion::Attach { .. } | ActiveMotion::Stop
+        ) {
+            let avoid_everything =
+                game.members[i].motion.handling() == Some(MovementHandling::TryNotToOverlap);
+            let blocked =
+                block_movement(&mut game.members, &game.assets, i, start, avoid_everything);
+            game.members[i].motion.rebound(blocked);
+        }
     }
 
+    update_particles(&mut game.particles);
+
+    update_camera(game);
+
     // TODO: Should do this after action or here?
     for member in game.members.iter_mut() {
         if let Some(new_sprite) = member.animation.update() {
@@ -2062,13 +3118,129 @@ pub fn update_game(
         }
 
         member.switch.apply(member.applied_switch);
+
+        member.count_timers();
     }
 
     game.frame_number += 1;
 
-    (events_to_apply, menu_actions)
+    Ok((events_to_apply, menu_actions))
+}
+
+// Which parts of a move were undone because they would have run into something
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct Blocked {
+    x: bool,
+    y: bool,
+}
+
+// Slides along whatever is in the way if only one direction is blocked.
+// Members that were already overlapping at the start don't block so nothing gets stuck
+fn block_movement(
+    members: &mut [Member],
+    assets: &Assets,
+    i: usize,
+    start: Vec2,
+    avoid_everything: bool,
+) -> Blocked {
+    let end = members[i].position;
+    if end == start {
+        return Blocked::default();
+    }
+
+    // Only members near where it's moving through can get in the way
+    let path = member_bounds(&members[i], assets);
+    members[i].position = start;
+    let path = path.union(member_bounds(&members[i], assets));
+    let blockers: Vec<usize> = (0..members.len())
+        .filter(|&j| j != i && (avoid_everything || members[j].solid))
+        .filter(|&j| member_bounds(&members[j], assets).collides(path))
+        .filter(|&j| !do_members_collide(&members[i], &members[j], assets))
+        .collect();
+
+    let mut is_clear_at = |position: Vec2| {
+        members[i].position = position;
+        blockers
+            .iter()
+            .all(|&j| !do_members_collide(&members[i], &members[j], assets))
+    };
+
+    if is_clear_at(end) {
+        Blocked::default()
+    } else if is_clear_at(Vec2::new(end.x, start.y)) {
+        Blocked { x: false, y: true }
+    } else if is_clear_at(Vec2::new(start.x, end.y)) {
+        Blocked { x: true, y: false }
+    } else {
+        members[i].position = start;
+        Blocked { x: true, y: true }
+    }
+}
+
+// Ping pong turns around at either end without stopping on the same point twice
+fn next_waypoint(len: usize, next: usize, forwards: bool, looping: PathLooping) -> (usize, bool) {
+    match looping {
+        PathLooping::Once => (next + 1, forwards),
+        PathLooping::Loop => ((next + 1) % len, forwards),
+        PathLooping::PingPong if len < 2 => (next, forwards),
+        PathLooping::PingPong if forwards && next + 1 == len => (next - 1, false),
+        PathLooping::PingPong if !forwards && next == 0 => (1, true),
+        PathLooping::PingPong if forwards => (next + 1, true),
+        PathLooping::PingPong => (next - 1, false),
+    }
+}
+
+// Where a member would be after jumping, it stays put if the location can't be found
+fn jump_position(
+    location: &JumpLocation,
+    member: &Member,
+    members: &[Member],
+    image: &Image,
+    rng: &mut SeededRng,
+    mouse: Mouse,
+) -> Vec2 {
+    match location {
+        JumpLocation::Point(position) => (*position).into(),
+        JumpLocation::Area(area) => {
+            // TODO: Handle size of object, (incl bg sizes? what does this mean)
+            log::debug!("AREA BEFORE: {:?}", area);
+            let area = constrained_area(image, member, *area);
+            log::debug!("AREA AFTER: {:?}", area);
+            let x = if area.w > 0.0 {
+                rng.number_in_range(area.x, area.x + area.w)
+            } else {
+                // TODO: ?
+                area.x
+            };
+            let y = if area.h > 0.0 {
+                rng.number_in_range(area.y, area.y + area.h)
+            } else {
+                // TODO: ?
+                area.y
+            };
+            Vec2::new(x, y)
+        }
+        JumpLocation::Mouse => mouse.position.into(),
+        JumpLocation::Member { name } => members
+            .iter()
+            .find(|other| other.name == *name)
+            .map(|other| other.position)
+            .unwrap_or(member.position),
+        JumpLocation::Tagged { tag } => members
+            .iter()
+            .filter(|other| other.has_tag(tag) && !std::ptr::eq(member, *other))
+            .collect::<Vec<_>>()
+            .choose(rng)
+            .map(|other| other.position)
+            .unwrap_or(member.position),
+        JumpLocation::Relative { offset } => {
+            let offset: Vec2 = (*offset).into();
+            member.position + offset
+        }
+    }
 }
 
+// Scrolled cameras give positions in the world rather than on the screen
 pub fn position_in_world(position: pixels::Position, camera: Camera) -> pixels::Position {
     let quad_camera = camera.to_quad_camera();
     quad_camera.screen_to_world(position.into()).into()
@@ -2085,8 +3257,46 @@ fn is_position_in_text(
     rect.contains_point(position)
 }
 
-fn is_position_in_member_image(position: pixels::Position, member: &Member, image: &Image) -> bool {
-    is_position_in_sprite_sheet_image(position, member.position.into(), member.sprite, image)
+fn is_position_in_member_image(
+    position: pixels::Position,
+    member: &Member,
+    assets: &Assets,
+) -> bool {
+    if member.sprite.size == SpriteSize::Empty {
+        return false;
+    }
+
+    let mask = assets.mask(member.sprite, member.transform);
+    collision_object(member, &mask).is_square_active(position)
+}
+
+fn collision_object<'a>(member: &Member, mask: &'a Mask) -> CollisionObject<'a, Mask> {
+    CollisionObject {
+        position: member.position.into(),
+        section: mask.section(),
+        grid: mask,
+    }
+}
+
+fn member_bounds(member: &Member, assets: &Assets) -> pixels::Rect {
+    match member.sprite.size {
+        SpriteSize::Empty => text_rect(member, &assets.font),
+        _ => coll::centred_bounds(
+            member.position.into(),
+            member
+                .transform
+                .size(sheet_source_rect(member.sprite).size()),
+        ),
+    }
+}
+
+fn text_rect(member: &Member, font: &BitmapFont) -> pixels::Rect {
+    pixels::Rect::xywh(
+        member.position.x,
+        member.position.y,
+        font.text_width(&member.text.contents),
+        font.char_height,
+    )
 }
 
 pub fn is_position_in_sprite_sheet_image(
@@ -2104,69 +3314,24 @@ pub fn is_position_in_sprite_sheet_image(
     is_adjusted_subsection_square_active(image, position, image_position, source_rect)
 }
 
-fn do_members_collide(a: &Member, b: &Member, image: &Image, font: &BitmapFont) -> bool {
-    match a.sprite.size {
-        SpriteSize::Empty => {
-            let a_rect = pixels::Rect::xywh(
-                a.position.x,
-                a.position.y,
-                font.text_width(&a.text.contents),
-                font.char_height,
-            );
-            match b.sprite.size {
-                SpriteSize::Empty => {
-                    let b_rect = pixels::Rect::xywh(
-                        b.position.x,
-                        b.position.y,
-                        font.text_width(&b.text.contents),
-                        font.char_height,
-                    );
-                    a_rect.collides(b_rect)
-                }
-                _ => {
-                    let b_source = sheet_source_rect(b.sprite);
-                    let obj = CollisionObject {
-                        position: b.position.into(),
-                        section: b_source,
-                        grid: image,
-                    };
-                    obj.collides_with_rect(a_rect)
-                }
-            }
+fn do_members_collide(a: &Member, b: &Member, assets: &Assets) -> bool {
+    match (a.sprite.size, b.sprite.size) {
+        (SpriteSize::Empty, SpriteSize::Empty) => {
+            text_rect(a, &assets.font).collides(text_rect(b, &assets.font))
+        }
+        (SpriteSize::Empty, _) => {
+            let b_mask = assets.mask(b.sprite, b.transform);
+            collision_object(b, &b_mask).collides_with_rect(text_rect(a, &assets.font))
+        }
+        (_, SpriteSize::Empty) => {
+            let a_mask = assets.mask(a.sprite, a.transform);
+            collision_object(a, &a_mask).collides_with_rect(text_rect(b, &assets.font))
+        }
+        _ => {
+            let a_mask = assets.mask(a.sprite, a.transform);
+            let b_mask = assets.mask(b.sprite, b.transform);
+            collision_object(a, &a_mask).collides_with_other(collision_object(b, &b_mask))
         }
-        _ => match b.sprite.size {
-            SpriteSize::Empty => {
-                let b_rect = pixels::Rect::xywh(
-                    b.position.x,
-                    b.position.y,
-                    font.text_width(&b.text.contents),
-                    font.char_height,
-                );
-                let a_source = sheet_source_rect(a.sprite);
-                let obj = CollisionObject {
-                    position: a.position.into(),
-                    section: a_source,
-                    grid: image,
-                };
-                obj.collides_with_rect(b_rect)
-            }
-            _ => {
-                let a_source = sheet_source_rect(a.sprite);
-                let a_obj = CollisionObject {
-                    position: a.position.into(),
-                    section: a_source,
-                    grid: image,
-                };
-                let b_source = sheet_source_rect(b.sprite);
-                let b_obj = CollisionObject {
-                    position: b.position.into(),
-                    section: b_source,
-                    grid: image,
-                };
-
-                a_obj.collides_with_other(b_obj)
-            }
-        },
     }
 }
 
@@ -2244,9 +3409,48 @@ fn get_pixel(image: &Image, x: u32, y: u32) -> Colour {
     }
 }
 
+// Grouped questions share the id of the top level question they're part of, each simple
+// question in it is told apart by its leaf, counted left to right whether it's asked or not
+fn is_question_true<F>(
+    question: &Question,
+    question_id: QuestionId,
+    leaf: usize,
+    is_simple_question_true: &mut F,
+) -> Result<bool, GameError>
+where
+    F: FnMut(&Question, QuestionId, usize) -> Result<bool, GameError>,
+{
+    Ok(match question {
+        Question::Not(question) => {
+            !is_question_true(question, question_id, leaf, is_simple_question_true)?
+        }
+        Question::AnyOf(questions) => {
+            let mut leaf = leaf;
+            for question in questions {
+                if is_question_true(question, question_id, leaf, is_simple_question_true)? {
+                    return Ok(true);
+                }
+                leaf += question.leaves().len();
+            }
+            false
+        }
+        Question::AllOf(questions) => {
+            let mut leaf = leaf;
+            for question in questions {
+                if !is_question_true(question, question_id, leaf, is_simple_question_true)? {
+                    return Ok(false);
+                }
+                leaf += question.leaves().len();
+            }
+            true
+        }
+        question => is_simple_question_true(question, question_id, leaf)?,
+    })
+}
+
 pub fn rename_in_todo_list(todo_list: &mut Vec<Chore>, from: &str, to: &str) {
     for chore in todo_list {
-        for question in &mut chore.questions {
+        for question in chore.questions.iter_mut().flat_map(Question::leaves_mut) {
             match question {
                 Question::IsSwitchSetTo { name, .. }
                 | Question::IsCollidingWith(CollisionWith::Member { name }) => {
@@ -2258,8 +3462,18 @@ pub fn rename_in_todo_list(todo_list: &mut Vec<Chore>, from: &str, to: &str) {
             }
         }
         for demand in &mut chore.demands {
+            // A spawn can name a member twice, as its template and where it appears
+            if let Demand::Spawn { template, .. } = demand {
+                if template == from {
+                    *template = to.to_owned();
+                }
+            }
             match demand {
-                Demand::Motion(Motion::JumpTo(JumpLocation::Member { name }))
+                Demand::Spawn {
+                    at: JumpLocation::Member { name },
+                    ..
+                }
+                | Demand::Motion(Motion::JumpTo(JumpLocation::Member { name }))
                 | Demand::Motion(Motion::Target { name, .. })
                 | Demand::Motion(Motion::AttachFromPositions { name, .. }) => {
                     if name == from {
@@ -2301,6 +3515,36 @@ impl QuestionId {
     }
 }
 
+#[derive(Debug)]
+pub enum GameError {
+    Question { id: QuestionId, message: String },
+    Demand { id: ChoreId, message: String },
+}
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GameError::Question { id, message } => write!(
+                f,
+                "Member {} Chore {} Question {}: {}",
+                id.member + 1,
+                id.chore + 1,
+                id.question + 1,
+                message
+            ),
+            GameError::Demand { id, message } => write!(
+                f,
+                "Member {} Chore {}: {}",
+                id.member + 1,
+                id.chore + 1,
+                message
+            ),
+        }
+    }
+}
+
+impl Error for GameError {}
+
 #[derive(Copy, Clone, Debug)]
 pub struct DemandId {
     pub member: usize,
@@ -2320,7 +3564,10 @@ impl DemandId {
 
 fn serialised_members(members: Vec<Member>) -> Vec<serial::Member> {
     let mut serialised_members = Vec::new();
-    for member in members {
+    for member in members
+        .into_iter()
+        .filter(|member| member.template.is_none())
+    {
         serialised_members.push(serial::Member {
             name: member.name.to_owned(),
             position: member.position.into(),
@@ -2334,40 +3581,44 @@ fn serialised_members(members: Vec<Member>) -> Vec<serial::Member> {
                     a: member.text.colour.a,
                 },
             },
+            tags: member.tags,
+            solid: member.solid,
+            layer: member.layer,
+            hidden: member.hidden,
+            transform: member.transform,
+            tint: member.tint.map(|tint| serial::Colour {
+                r: tint.r,
+                g: tint.g,
+                b: tint.b,
+                a: tint.a,
+            }),
+            state: member.state,
             todo_list: {
                 let mut todo_list = Vec::new();
                 for chore in &member.todo_list {
-                    todo_list.push(serial::Chore {
-                        questions: chore.questions.to_vec(),
-                        demands: chore.demands.to_vec(),
-                    });
-                }
-
-                for chore in todo_list.iter_mut().take(serial::CHORE_COUNT) {
-                    for question_index in (0..serial::QUESTION_COUNT).rev() {
-                        if chore.questions[question_index] == Question::None {
-                            chore.questions.remove(question_index);
-                        } else {
-                            break;
-                        }
+                    let mut questions = chore.questions.clone();
+                    while questions.last() == Some(&Question::None) {
+                        questions.pop();
                     }
-                    for demand_index in (0..serial::DEMAND_COUNT).rev() {
-                        if chore.demands[demand_index] == Demand::None {
-                            chore.demands.remove(demand_index);
-                        } else {
-                            break;
-                        }
+                    let mut demands = chore.demands.clone();
+                    while demands.last() == Some(&Demand::None) {
+                        demands.pop();
                     }
+                    todo_list.push(serial::Chore {
+                        label: chore.label.clone(),
+                        state: chore.state.clone(),
+                        questions,
+                        demands,
+                    });
                 }
 
-                for chore_index in (0..serial::CHORE_COUNT).rev() {
-                    if todo_list[chore_index].questions.is_empty()
-                        && todo_list[chore_index].demands.is_empty()
-                    {
-                        todo_list.remove(chore_index);
-                    } else {
-                        break;
-                    }
+                while todo_list.last().is_some_and(|chore| {
+                    chore.label.is_none()
+                        && chore.state.is_none()
+                        && chore.questions.is_empty()
+                        && chore.demands.is_empty()
+                }) {
+                    todo_list.pop();
                 }
                 todo_list
             },
@@ -2392,30 +3643,25 @@ fn playable_members(members: Vec<serial::Member>) -> Vec<Member> {
                     a: member.text.colour.a,
                 },
             },
-            todo_list: {
-                let mut todo_list = default_todo_list();
-                for (chore_index, chore) in
-                    todo_list.iter_mut().enumerate().take(serial::CHORE_COUNT)
-                {
-                    for question_index in 0..serial::QUESTION_COUNT {
-                        chore.questions[question_index] = member
-                            .todo_list
-                            .get(chore_index)
-                            .and_then(|chore| chore.questions.get(question_index))
-                            .cloned()
-                            .unwrap_or(Question::None);
-                    }
-                    for demand_index in 0..serial::DEMAND_COUNT {
-                        chore.demands[demand_index] = member
-                            .todo_list
-                            .get(chore_index)
-                            .and_then(|chore| chore.demands.get(demand_index))
-                            .cloned()
-                            .unwrap_or(Demand::None);
-                    }
-                }
-                todo_list
-            },
+            todo_list: member
+                .todo_list
+                .into_iter()
+                .map(|chore| Chore {
+                    label: chore.label,
+                    state: chore.state,
+                    questions: chore.questions,
+                    demands: chore.demands,
+                })
+                .collect(),
+            tags: member.tags,
+            solid: member.solid,
+            layer: member.layer,
+            hidden: member.hidden,
+            transform: member.transform,
+            tint: member
+                .tint
+                .map(|tint| Colour::new(tint.r, tint.g, tint.b, tint.a)),
+            state: member.state,
             ..Default::default()
         });
     }
@@ -2430,11 +3676,13 @@ pub fn cartridge_from_game(game: Game) -> Cartridge {
     });
 
     Cartridge {
-        format_version: 0,
+        format_version: CURRENT_FORMAT_VERSION,
         members: serialised_members(game.members),
         published: true,
         length: game.length,
         size: game.size,
+        world_size: game.world_size,
+        scales_with_difficulty: game.scales_with_difficulty,
         intro_text: game.intro_text,
         font: game.assets.font_string,
         image: game.assets.image_string,
@@ -2444,17 +3692,35 @@ pub fn cartridge_from_game(game: Game) -> Cartridge {
     }
 }
 
-pub fn game_from_cartridge(cartridge: Cartridge, rng: SeededRng) -> Game {
+pub fn game_from_cartridge(cartridge: Cartridge, rng: SeededRng) -> WhyResult<Game> {
+    game_from_cartridge_with(cartridge, rng, Assets::from_strings)
+}
+
+// For running games without a window, e.g. automated playthroughs
+pub fn headless_game_from_cartridge(cartridge: Cartridge, rng: SeededRng) -> WhyResult<Game> {
+    game_from_cartridge_with(cartridge, rng, Assets::headless_from_strings)
+}
+
+fn game_from_cartridge_with(
+    cartridge: Cartridge,
+    rng: SeededRng,
+    assets_from_strings: fn(
+        ImageString,
+        ImageString,
+        Option<SoundString>,
+        AssetFilenames,
+    ) -> WhyResult<Assets>,
+) -> WhyResult<Game> {
     // TODO: Don't lose looped info
     let music = cartridge.music.map(|music| music.data);
-    Game {
+    Ok(Game {
         members: playable_members(cartridge.members),
-        assets: Assets::from_strings(
+        assets: assets_from_strings(
             cartridge.image,
             cartridge.font,
             music,
             cartridge.asset_filenames,
-        ),
+        )?,
         size: cartridge.size,
         length: cartridge.length,
         win_status: WinStatus::NotYetWon,
@@ -2462,5 +3728,570 @@ pub fn game_from_cartridge(cartridge: Cartridge, rng: SeededRng) -> Game {
         frame_number: 0,
         intro_text: cartridge.intro_text,
         rng,
+        numbers: HashMap::new(),
+        error: None,
+        spawn_count: 0,
+        templates: HashMap::new(),
+        particles: Vec::new(),
+        world_size: cartridge.world_size,
+        camera: WorldCamera::default(),
+        scales_with_difficulty: cartridge.scales_with_difficulty,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inp::{Button, Input, RECORDED_KEYS};
+    use crate::replay::{Replay, Session};
+
+    #[test]
+    fn test_grouped_questions() {
+        let question: Question = serde_json::from_str(
+            r#"{"AnyOf": [{"Not": "IsOnWeb"}, {"AllOf": ["IsOnWeb", "IsOnDesktop"]}]}"#,
+        )
+        .unwrap();
+        let mut is_on_web = |question: &Question, _, _| -> Result<bool, GameError> {
+            Ok(*question == Question::IsOnWeb)
+        };
+
+        let id = QuestionId::new(0, 0, 0);
+        assert!(!is_question_true(&question, id, 0, &mut is_on_web).unwrap());
+        assert!(
+            is_question_true(&Question::Not(Box::new(question)), id, 0, &mut is_on_web).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_grouped_question_leaves() {
+        let question: Question = serde_json::from_str(
+            r#"{"AllOf": [{"AnyOf": ["IsOnWeb", "IsOnDesktop"]}, {"Not": "IsOnWeb"}]}"#,
+        )
+        .unwrap();
+        let mut asked = Vec::new();
+        let mut is_on_web = |question: &Question, _, leaf| -> Result<bool, GameError> {
+            asked.push(leaf);
+            Ok(*question == Question::IsOnWeb)
+        };
+
+        is_question_true(&question, QuestionId::new(0, 0, 0), 0, &mut is_on_web).unwrap();
+        // The skipped IsOnDesktop still takes up leaf 1
+        assert_eq!(asked, vec![0, 2]);
+    }
+
+    #[test]
+    fn test_group_and_ungroup_questions() {
+        let mut chore = Chore {
+            questions: vec![Question::IsOnWeb, Question::IsOnDesktop, Question::None],
+            ..Default::default()
+        };
+
+        assert!(chore.group_question_with_next(0, Group::All));
+        assert!(chore.group_question_with_next(0, Group::Any));
+        assert_eq!(
+            chore.questions,
+            vec![Question::AnyOf(vec![
+                Question::AllOf(vec![Question::IsOnWeb, Question::IsOnDesktop]),
+                Question::None,
+            ])]
+        );
+        assert!(!chore.group_question_with_next(0, Group::Any));
+
+        assert!(chore.ungroup_question(0));
+        assert!(!chore.ungroup_question(1));
+        assert_eq!(
+            chore.questions,
+            vec![
+                Question::AllOf(vec![Question::IsOnWeb, Question::IsOnDesktop]),
+                Question::None,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_particles_are_deterministic() {
+        let emit = |seed| {
+            let mut rng = SeededRng::new(seed);
+            let style = ParticleStyle::default();
+            emit_particles(Vec2::ZERO, style, 8, Speed::Fast, 90, 3, &mut rng)
+        };
+        let velocities = |particles: &[Particle]| {
+            particles
+                .iter()
+                .map(|particle| particle.velocity)
+                .collect::<Vec<_>>()
+        };
+
+        let mut particles = emit(7);
+        assert_eq!(velocities(&particles), velocities(&emit(7)));
+        assert!(particles.iter().all(|particle| particle.velocity.y < 0.0));
+
+        update_particles(&mut particles);
+        update_particles(&mut particles);
+        assert_eq!(particles.len(), 8);
+        update_particles(&mut particles);
+        assert!(particles.is_empty());
+    }
+
+    #[test]
+    fn test_next_waypoint() {
+        let visit = |looping| {
+            let (mut next, mut forwards) = (0, true);
+            let mut visited = vec![next];
+            for _ in 0..5 {
+                (next, forwards) = next_waypoint(3, next, forwards, looping);
+                visited.push(next);
+            }
+            visited
+        };
+
+        assert_eq!(visit(PathLooping::Once), vec![0, 1, 2, 3, 4, 5]);
+        assert_eq!(visit(PathLooping::Loop), vec![0, 1, 2, 0, 1, 2]);
+        assert_eq!(visit(PathLooping::PingPong), vec![0, 1, 2, 1, 0, 1]);
+    }
+
+    #[test]
+    fn test_tagged_group() {
+        let coin = |switch| Member {
+            switch,
+            tags: vec!["coin".to_owned()],
+            ..Default::default()
+        };
+        let members = [coin(Switch::On), coin(Switch::Off), Member::default()];
+        let coins = || members.iter().filter(|member| member.has_tag("coin"));
+        let is_on = |member: &Member| is_switch_set_to(member.switch, Switch::On);
+
+        assert!(does_group_match(Group::Any, coins(), is_on));
+        assert!(!does_group_match(Group::All, coins(), is_on));
+        assert!(!does_group_match(
+            Group::All,
+            std::iter::empty::<&Member>(),
+            is_on
+        ));
+    }
+
+    #[test]
+    fn test_camera_scroll() {
+        assert_eq!(dead_zone_push(10.0, 16.0), 0.0);
+        assert_eq!(dead_zone_push(20.0, 16.0), 4.0);
+        assert_eq!(dead_zone_push(-20.0, 16.0), -4.0);
+
+        assert_eq!(clamp_scroll(-5.0, 0, 512, 256), 0.0);
+        assert_eq!(clamp_scroll(300.0, 0, 512, 256), 256.0);
+        assert_eq!(clamp_scroll(300.0, 0, 100, 256), 0.0);
+    }
+
+    #[test]
+    fn test_messages_last_one_frame() {
+        let mut members = vec![
+            Member {
+                name: "Key".to_owned(),
+                ..Default::default()
+            },
+            Member {
+                name: "Door".to_owned(),
+                ..Default::default()
+            },
+        ];
+
+        deliver_messages(
+            &mut members,
+            vec![
+                ("Collected".to_owned(), Some("Door".to_owned())),
+                ("Start".to_owned(), None),
+                ("Hello".to_owned(), Some("Ghost".to_owned())),
+            ],
+        );
+        assert!(members.iter().all(|member| !member.inbox.contains("Hello")));
+        assert!(!members[0].inbox.contains("Collected"));
+        assert!(members[1].inbox.contains("Collected"));
+        assert!(members.iter().all(|member| member.inbox.contains("Start")));
+
+        deliver_messages(&mut members, Vec::new());
+        assert!(members.iter().all(|member| member.inbox.is_empty()));
+    }
+
+    #[test]
+    fn test_run_until_wait() {
+        let demands = vec![
+            Demand::Show,
+            Demand::Wait { frames: 30 },
+            Demand::Hide,
+            Demand::Wait { frames: 10 },
+            Demand::Destroy,
+        ];
+        let mut runnable = Vec::new();
+
+        let sequence = run_until_wait(2, &demands, &mut runnable).unwrap();
+        assert_eq!(runnable, vec![(2, Demand::Show)]);
+        assert_eq!(sequence.frames_left, 30);
+
+        let sequence = run_until_wait(2, &sequence.demands, &mut runnable).unwrap();
+        assert_eq!(runnable, vec![(2, Demand::Show), (2, Demand::Hide)]);
+        assert_eq!(sequence.frames_left, 10);
+
+        assert!(run_until_wait(2, &sequence.demands, &mut runnable).is_none());
+        assert_eq!(runnable.last(), Some(&(2, Demand::Destroy)));
+    }
+
+    #[test]
+    fn test_scaled_for_difficulty() {
+        let go = |speed| {
+            Demand::Motion(Motion::Go {
+                direction: HashSet::from([Direction::East]),
+                speed,
+            })
+        };
+
+        assert_eq!(
+            scaled_for_difficulty(go(Speed::Normal), DifficultyLevel::Normal),
+            go(Speed::Normal)
+        );
+        assert_eq!(
+            scaled_for_difficulty(go(Speed::Normal), DifficultyLevel::Tough),
+            go(Speed::VeryFast)
+        );
+        assert_eq!(
+            scaled_for_difficulty(go(Speed::Fast), DifficultyLevel::Tough),
+            go(Speed::VeryFast)
+        );
+        assert_eq!(
+            scaled_for_difficulty(Demand::Show, DifficultyLevel::Tough),
+            Demand::Show
+        );
+    }
+
+    #[test]
+    fn test_replay_round_trip() {
+        let image_string =
+            |filename: &str| ImageString(BaseEncoder.encode(std::fs::read(filename).unwrap()));
+        let cartridge = Cartridge {
+            members: vec![serial::Member {
+                name: "Bug".to_owned(),
+                position: pixels::Position::new(128, 72),
+                sprite: Sprite {
+                    index: 0,
+                    size: SpriteSize::Square(16),
+                },
+                todo_list: vec![
+                    serial::Chore {
+                        questions: vec![Question::IsTimeAt(When::Start)],
+                        demands: vec![Demand::Motion(Motion::Roam {
+                            roam_type: RoamType::Wiggle,
+                            area: pixels::Rect::aabb(0, 0, 256, 144),
+                            speed: Speed::Fast,
+                            movement_handling: MovementHandling::Anywhere,
+                        })],
+                        ..Default::default()
+                    },
+                    serial::Chore {
+                        questions: vec![Question::IsMouseInteracting {
+                            which: WhichButton::Left,
+                            state: Some(Button::Press),
+                            hover: Hover::Anywhere,
+                        }],
+                        demands: vec![Demand::Motion(Motion::JumpTo(JumpLocation::Mouse))],
+                        ..Default::default()
+                    },
+                ],
+                ..Default::default()
+            }],
+            ..Cartridge::new(
+                Size::Small,
+                image_string("images/black.png"),
+                image_string("fonts/pixolletta.png"),
+            )
+        };
+        let new_input = || Input {
+            outer: Mouse::default(),
+            inner: Mouse::default(),
+            rmb_held_down_for: 0,
+            chars_pressed: Vec::new(),
+            mouse_scroll: 0.0,
+            keyboard: HashMap::from(RECORDED_KEYS.map(|key| (key, RepeatableButton::default()))),
+        };
+        let run_frame = |game: &mut Game, mouse: Mouse| {
+            update_game(
+                game,
+                mouse,
+                &HashMap::new(),
+                &mut SoundQueue::Ready {
+                    sounds: HashSet::new(),
+                },
+                &mut Editor::default(),
+                &mut Environment {
+                    score: 0,
+                    difficulty_level: DifficultyLevel::default(),
+                    playback_rate: 1.0,
+                    context: HashMap::new(),
+                    rng: SeededRng::new(0),
+                },
+                &mut Navigation::new(Link::default()),
+                &mut DrawTool::headless(),
+                &mut MusicMaker::init(),
+                None,
+                &HashSet::new(),
+            )
+            .unwrap();
+            game.members[0].position
+        };
+
+        let mut session = Session::Recording {
+            replay: Replay::new(7),
+            filename: String::new(),
+        };
+        let mut game = headless_game_from_cartridge(cartridge.clone(), SeededRng::new(7)).unwrap();
+        let mut input = new_input();
+        let mut recorded_positions = Vec::new();
+        for frame in 0..60 {
+            let position = pixels::Position::new(frame * 4, 100 - frame);
+            input.inner.update(position, frame % 20 == 10, false, false);
+            session.handle_input(&mut input);
+            recorded_positions.push(run_frame(&mut game, input.inner));
+        }
+
+        let Session::Recording { replay, .. } = session else {
+            unreachable!()
+        };
+        let replay = Replay::from_file_contents(&serde_json::to_string(&replay).unwrap()).unwrap();
+        let mut game =
+            headless_game_from_cartridge(cartridge, SeededRng::new(replay.seed)).unwrap();
+        let mut session = Session::Replaying { replay, frame: 0 };
+        let mut input = new_input();
+        let mut replayed_positions = Vec::new();
+        while !session.is_replay_finished() {
+            session.handle_input(&mut input);
+            replayed_positions.push(run_frame(&mut game, input.inner));
+        }
+
+        assert_eq!(recorded_positions, replayed_positions);
+
+        // Recordings from before the movement keys were added can't be replayed
+        assert!(Replay::from_file_contents(r#"{"version": 1, "seed": 7, "frames": []}"#).is_err());
+    }
+
+    #[test]
+    fn test_timers() {
+        let mut member = Member::default();
+        member.timers.insert("Fuse".to_owned(), 0);
+
+        for _ in 0..9 {
+            member.count_timers();
+        }
+        assert!(!member.has_timer_passed("Fuse", 10));
+        member.count_timers();
+        assert!(member.has_timer_passed("Fuse", 10));
+        assert!(!member.has_timer_passed("Stopped", 0));
+    }
+
+    #[test]
+    fn test_spawn_from_destroyed_template() {
+        let members = vec![
+            Member {
+                name: "Gun".to_owned(),
+                ..Default::default()
+            },
+            Member {
+                name: "Bullet #1".to_owned(),
+                template: Some("Bullet".to_owned()),
+                ..Default::default()
+            },
+        ];
+        let templates = HashMap::from([(
+            "Bullet".to_owned(),
+            Member {
+                name: "Bullet".to_owned(),
+                ..Default::default()
+            },
+        )]);
+
+        assert_eq!(
+            get_template(&members, &templates, "Gun").unwrap().name,
+            "Gun"
+        );
+        assert_eq!(
+            get_template(&members, &templates, "Bullet").unwrap().name,
+            "Bullet"
+        );
+        assert!(get_template(&members, &templates, "Wall").is_err());
+        // Instances collide as their template even once it's gone
+        assert!(members[1].is_called("Bullet"));
+    }
+
+    #[test]
+    fn test_destroy_selected_member() {
+        let image_string =
+            |filename: &str| ImageString(BaseEncoder.encode(std::fs::read(filename).unwrap()));
+        let member = |name: &str, todo_list| serial::Member {
+            name: name.to_owned(),
+            todo_list,
+            ..Default::default()
+        };
+        let cartridge = Cartridge {
+            members: vec![
+                member("Player", Vec::new()),
+                member(
+                    "Enemy",
+                    vec![serial::Chore {
+                        questions: vec![Question::IsTimeAt(When::Start)],
+                        demands: vec![Demand::Destroy],
+                        ..Default::default()
+                    }],
+                ),
+            ],
+            ..Cartridge::new(
+                Size::Small,
+                image_string("images/black.png"),
+                image_string("fonts/pixolletta.png"),
+            )
+        };
+        let mut game = headless_game_from_cartridge(cartridge, SeededRng::new(0)).unwrap();
+        let mut editor = Editor {
+            selected_index: 1,
+            ..Default::default()
+        };
+
+        let selected_name = editor.selected_name(&game.members);
+        update_game(
+            &mut game,
+            Mouse::default(),
+            &HashMap::new(),
+            &mut SoundQueue::Ready {
+                sounds: HashSet::new(),
+            },
+            &mut Editor::default(),
+            &mut Environment {
+                score: 0,
+                difficulty_level: DifficultyLevel::default(),
+                playback_rate: 1.0,
+                context: HashMap::new(),
+                rng: SeededRng::new(0),
+            },
+            &mut Navigation::new(Link::default()),
+            &mut DrawTool::headless(),
+            &mut MusicMaker::init(),
+            None,
+            &HashSet::new(),
+        )
+        .unwrap();
+        editor.reselect(&game.members, selected_name);
+
+        assert_eq!(game.members.len(), 1);
+        assert_eq!(
+            editor.selected_name(&game.members).as_deref(),
+            Some("Player")
+        );
+    }
+
+    #[test]
+    fn test_block_movement() {
+        let image_string =
+            |filename: &str| ImageString(BaseEncoder.encode(std::fs::read(filename).unwrap()));
+        // A white sheet, so every sprite is solid all over
+        let assets = Assets {
+            image: Image::gen_image_color(512, 512, colours::WHITE),
+            ..Assets::headless_from_strings(
+                image_string("images/black.png"),
+                image_string("fonts/pixolletta.png"),
+                None,
+                AssetFilenames::default(),
+            )
+            .unwrap()
+        };
+        let square = |name: &str, x: f32, solid: bool| Member {
+            name: name.to_owned(),
+            position: Vec2::new(x, 72.0),
+            sprite: Sprite {
+                index: 0,
+                size: SpriteSize::Square(16),
+            },
+            solid,
+            ..Default::default()
+        };
+        let mut members = vec![square("Mover", 100.0, false), square("Wall", 120.0, true)];
+        let mut move_by = |start: Vec2, offset: Vec2| {
+            members[0].position = start + offset;
+            let blocked = block_movement(&mut members, &assets, 0, start, false);
+            (blocked, members[0].position)
+        };
+        let start = Vec2::new(100.0, 72.0);
+
+        // Stops short of the wall
+        assert_eq!(
+            move_by(start, Vec2::new(6.0, 0.0)),
+            (Blocked { x: true, y: false }, start)
+        );
+        // Slides along it
+        assert_eq!(
+            move_by(start, Vec2::new(6.0, 6.0)),
+            (Blocked { x: true, y: false }, Vec2::new(100.0, 78.0))
+        );
+        // Can move around while already overlapping it
+        assert_eq!(
+            move_by(Vec2::new(110.0, 72.0), Vec2::new(2.0, 0.0)),
+            (Blocked::default(), Vec2::new(112.0, 72.0))
+        );
+
+        members[1].solid = false;
+        members[0].position = Vec2::new(106.0, 72.0);
+        assert_eq!(
+            block_movement(&mut members, &assets, 0, start, false),
+            Blocked::default()
+        );
+        assert_eq!(members[0].position, Vec2::new(106.0, 72.0));
+    }
+
+    #[test]
+    fn test_rebound() {
+        let mut motion = ActiveMotion::Reflect {
+            area: pixels::Rect::aabb(0, 0, 256, 144),
+            speed: Speed::Normal,
+            handling: MovementHandling::Anywhere,
+            velocity: Vec2::new(2.0, 1.0),
+        };
+
+        motion.rebound(Blocked { x: true, y: false });
+        assert!(matches!(
+            motion,
+            ActiveMotion::Reflect { velocity, .. } if velocity == Vec2::new(-2.0, 1.0)
+        ));
+        motion.rebound(Blocked { x: true, y: true });
+        assert!(matches!(
+            motion,
+            ActiveMotion::Reflect { velocity, .. } if velocity == Vec2::new(2.0, -1.0)
+        ));
+    }
+
+    #[test]
+    fn test_resume_sequences() {
+        let mut member = Member::default();
+        let demands = [Demand::Wait { frames: 2 }, Demand::Show];
+        member
+            .sequences
+            .extend(run_until_wait(1, &demands, &mut Vec::new()));
+
+        assert!(member.is_waiting_on(1));
+        assert!(!member.is_waiting_on(0));
+        assert_eq!(member.resume_sequences(), Vec::new());
+        assert_eq!(member.resume_sequences(), vec![(1, Demand::Show)]);
+        assert!(!member.is_waiting_on(1));
+    }
+
+    #[test]
+    fn test_member_states() {
+        let mut frog = Member {
+            state: Some("Idle".to_owned()),
+            ..Default::default()
+        };
+        assert!(frog.is_in_state("Idle"));
+        assert!(!frog.just_entered_state);
+
+        frog.enter_state("Jumping".to_owned());
+        assert!(frog.is_in_state("Jumping"));
+        assert!(frog.just_entered_state);
+
+        frog.just_entered_state = false;
+        frog.enter_state("Jumping".to_owned());
+        assert!(!frog.just_entered_state);
     }
 }
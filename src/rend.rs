@@ -1,2 +1,9 @@
 pub use macroquad::texture::Image;
 pub use macroquad::texture::Texture2D as Texture;
+
+use macroquad::miniquad::{RawId, TextureId};
+
+/// Stand-in for when there is no graphics context, it must never be drawn
+pub fn headless_texture() -> Texture {
+    Texture::from_miniquad_texture(TextureId::from_raw_id(RawId::OpenGl(0)))
+}
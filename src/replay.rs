@@ -0,0 +1,120 @@
+use serde::{Deserialize, Serialize};
+
+use crate::err::WhyResult;
+use crate::inp::{Input, Mouse, RepeatableButton, RECORDED_KEYS};
+
+// Goes up whenever RECORDED_KEYS changes, as recordings only keep the buttons in that order
+pub const REPLAY_VERSION: u32 = 2;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedInput {
+    pub outer: Mouse,
+    pub inner: Mouse,
+    pub rmb_held_down_for: i32,
+    pub chars_pressed: Vec<char>,
+    pub mouse_scroll: f32,
+    // In the same order as RECORDED_KEYS
+    pub keyboard: Vec<RepeatableButton>,
+}
+
+impl RecordedInput {
+    pub fn new(input: &Input) -> RecordedInput {
+        RecordedInput {
+            outer: input.outer,
+            inner: input.inner,
+            rmb_held_down_for: input.rmb_held_down_for,
+            chars_pressed: input.chars_pressed.clone(),
+            mouse_scroll: input.mouse_scroll,
+            keyboard: RECORDED_KEYS
+                .iter()
+                .map(|key| input.keyboard.get(key).copied().unwrap_or_default())
+                .collect(),
+        }
+    }
+
+    pub fn apply(&self, input: &mut Input) {
+        input.outer = self.outer;
+        input.inner = self.inner;
+        input.rmb_held_down_for = self.rmb_held_down_for;
+        input.chars_pressed = self.chars_pressed.clone();
+        input.mouse_scroll = self.mouse_scroll;
+        for (key, button) in RECORDED_KEYS.iter().zip(&self.keyboard) {
+            input.keyboard.insert(*key, *button);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Replay {
+    pub version: u32,
+    pub seed: u64,
+    pub frames: Vec<RecordedInput>,
+}
+
+impl Replay {
+    pub fn new(seed: u64) -> Replay {
+        Replay {
+            version: REPLAY_VERSION,
+            seed,
+            frames: Vec::new(),
+        }
+    }
+
+    pub fn from_file_contents(file_contents: &str) -> WhyResult<Replay> {
+        let replay: Replay = serde_json::from_str(file_contents)
+            .map_err(|e| format!("Error deserialising replay: {:?}", e))?;
+
+        if replay.version != REPLAY_VERSION {
+            return Err(format!(
+                "Replay version {} is not supported, expected {}",
+                replay.version, REPLAY_VERSION
+            )
+            .into());
+        }
+
+        Ok(replay)
+    }
+
+    pub fn save(&self, filename: &str) -> WhyResult<()> {
+        let s = serde_json::to_string(self)?;
+        std::fs::write(filename, s)?;
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub enum Session {
+    Live,
+    Recording { replay: Replay, filename: String },
+    Replaying { replay: Replay, frame: usize },
+}
+
+impl Session {
+    // Replaces or records the input that the next metagame update will see
+    pub fn handle_input(&mut self, input: &mut Input) {
+        match self {
+            Session::Live => {}
+            Session::Recording { replay, .. } => replay.frames.push(RecordedInput::new(input)),
+            Session::Replaying { replay, frame } => {
+                if let Some(recorded) = replay.frames.get(*frame) {
+                    recorded.apply(input);
+                    *frame += 1;
+                }
+            }
+        }
+    }
+
+    pub fn is_replay_finished(&self) -> bool {
+        match self {
+            Session::Replaying { replay, frame } => *frame >= replay.frames.len(),
+            _ => false,
+        }
+    }
+
+    pub fn finish(&self) -> WhyResult<()> {
+        if let Session::Recording { replay, filename } = self {
+            replay.save(filename)?;
+        }
+        Ok(())
+    }
+}
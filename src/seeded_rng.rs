@@ -61,6 +61,11 @@ impl SeededRng {
         self.seed
     }
 
+    // A new generator seeded from this one, so everything stays reproducible from one seed
+    pub fn next_rng(&mut self) -> SeededRng {
+        SeededRng::new(self.rand() as u64)
+    }
+
     fn rand(&mut self) -> u32 {
         let oldstate = self.state;
         self.state = self
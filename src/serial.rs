@@ -1,24 +1,22 @@
 use crate::art::SpriteSize;
 use crate::err::WhyResult;
+use crate::migrate::migrate;
 use crate::nav::Link;
 use crate::FileSystem;
 
-use super::meta::{INNER_CENTRE, OUTER_CENTRE};
+use super::meta::{INNER_CENTRE, INNER_SIZE, OUTER_CENTRE, OUTER_SIZE};
 use super::pixels;
 //use super::{colours, Colour};
 use super::anim::AnimationStyle;
-use super::art::Sprite;
+use super::art::{Rotation, Sprite, Transform};
 use super::common::Speed;
-use super::inp::Button;
+use super::inp::{Button, Control, Key};
 use macroquad::logging as log;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use strum_macros::EnumString;
 
 pub const ANIMATION_SPRITE_COUNT: usize = 8;
-pub const CHORE_COUNT: usize = 6;
-pub const QUESTION_COUNT: usize = 6;
-pub const DEMAND_COUNT: usize = 6;
 
 #[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
 pub struct ImageString(pub String);
@@ -57,6 +55,31 @@ pub enum GameSize {
     Big,
 }
 
+impl GameSize {
+    pub fn screen_size(self) -> pixels::Size {
+        match self {
+            GameSize::Small => INNER_SIZE,
+            GameSize::Big => OUTER_SIZE,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, PartialEq, EnumString)]
+#[repr(usize)]
+pub enum DifficultyLevel {
+    #[default]
+    Normal = 0,
+    Challenge = 1,
+    Tough = 2,
+}
+
+// Worlds smaller than the screen are drawn as if they were the screen size
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct WorldSize {
+    pub width: u32,
+    pub height: u32,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, EnumString)]
 pub enum IntroText {
     Same(String),
@@ -85,6 +108,11 @@ pub struct Cartridge {
     pub length: Length,
     pub size: GameSize,
     #[serde(default)]
+    pub world_size: Option<WorldSize>,
+    // Harder difficulties make every motion and animation faster
+    #[serde(default)]
+    pub scales_with_difficulty: bool,
+    #[serde(default)]
     pub intro_text: IntroText,
     pub font: ImageString,
     pub image: ImageString,
@@ -140,7 +168,10 @@ impl Cartridge {
     }
 
     pub fn from_file_contents(file_contents: &str) -> WhyResult<Cartridge> {
-        serde_json::from_str(file_contents)
+        let mut value = serde_json::from_str(file_contents)
+            .map_err(|e| format!("Error deserialising game: {:?}", e))?;
+        migrate(&mut value)?;
+        serde_json::from_value(value)
             .map_err(|e| format!("Error deserialising game: {:?}", e).into())
     }
 }
@@ -152,10 +183,32 @@ pub struct Member {
     pub sprite: Sprite,
     pub text: Text,
     pub todo_list: Vec<Chore>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    // Solid members stop anything moving into them
+    #[serde(default)]
+    pub solid: bool,
+    // Higher layers are drawn on top, members on the same layer are drawn in list order
+    #[serde(default)]
+    pub layer: i32,
+    #[serde(default)]
+    pub hidden: bool,
+    #[serde(default)]
+    pub transform: Transform,
+    #[serde(default)]
+    pub tint: Option<Colour>,
+    // The named state the member starts in
+    #[serde(default)]
+    pub state: Option<String>,
 }
 
 #[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
 pub struct Chore {
+    #[serde(default)]
+    pub label: Option<String>,
+    // Only checked while the member is in this state, chores without one are always checked
+    #[serde(default)]
+    pub state: Option<String>,
     pub questions: Vec<Question>,
     pub demands: Vec<Demand>,
 }
@@ -170,13 +223,42 @@ pub enum Question {
         state: Option<Button>,
         hover: Hover,
     },
+    IsKeyInteracting {
+        key: Key,
+        state: Option<Button>,
+    },
+    IsControlInteracting {
+        control: Control,
+        state: Option<Button>,
+    },
     IsSwitchSetTo {
         name: String,
         switch: Switch,
     },
+    IsTaggedSwitchSetTo {
+        tag: String,
+        group: Group,
+        switch: Switch,
+    },
     IsWinStatusSetTo(WinStatus),
     IsSpriteSetTo(Sprite),
     IsAnimationFinished,
+    IsPathFinished,
+    IsVisible,
+    IsInState {
+        state: String,
+    },
+    // Only on the first frame after entering it
+    JustEnteredState {
+        state: String,
+    },
+    HasReceived {
+        message: String,
+    },
+    HasTimerPassed {
+        name: String,
+        frames: usize,
+    },
     IsCollidingWith(CollisionWith),
     IsTextSetTo {
         value: String,
@@ -185,6 +267,23 @@ pub enum Question {
         name: String,
         value: String,
     },
+    IsNumberEqualTo {
+        name: String,
+        value: i32,
+    },
+    IsNumberLessThan {
+        name: String,
+        value: i32,
+    },
+    IsNumberGreaterThan {
+        name: String,
+        value: i32,
+    },
+    IsNumberBetween {
+        name: String,
+        min: i32,
+        max: i32,
+    },
     IsPagedVariableSelected {
         name: String,
         value: String,
@@ -199,8 +298,37 @@ pub enum Question {
     IsSubgamePlaying,
     IsSubgameEnding,
     IsShortcutUsed(Shortcut),
+    IsDifficulty(DifficultyLevel),
     IsOnDesktop,
     IsOnWeb,
+    // Grouping
+    Not(Box<Question>),
+    AnyOf(Vec<Question>),
+    AllOf(Vec<Question>),
+}
+
+impl Question {
+    // The questions inside any grouping, for when only what is being asked matters
+    pub fn leaves(&self) -> Vec<&Question> {
+        match self {
+            Question::Not(question) => question.leaves(),
+            Question::AnyOf(questions) | Question::AllOf(questions) => {
+                questions.iter().flat_map(Question::leaves).collect()
+            }
+            question => vec![question],
+        }
+    }
+
+    pub fn leaves_mut(&mut self) -> Vec<&mut Question> {
+        match self {
+            Question::Not(question) => question.leaves_mut(),
+            Question::AnyOf(questions) | Question::AllOf(questions) => questions
+                .iter_mut()
+                .flat_map(Question::leaves_mut)
+                .collect(),
+            question => vec![question],
+        }
+    }
 }
 
 #[allow(clippy::enum_variant_names)]
@@ -210,7 +338,21 @@ pub enum Demand {
     None,
     // Game Stuff
     SetSprite(Sprite),
+    SetFlip {
+        x: bool,
+        y: bool,
+    },
+    SetRotation(Rotation),
+    SetScale {
+        scale: u32,
+    },
+    SetTint(Colour),
+    ClearTint,
     SetSwitch(Switch),
+    SetTaggedSwitch {
+        tag: String,
+        switch: Switch,
+    },
     SetText(Text),
     Win,
     Lose,
@@ -220,12 +362,86 @@ pub enum Demand {
         sprites: Vec<Sprite>,
     },
     StopAnimation,
+    StartTimer {
+        name: String,
+    },
+    StopTimer {
+        name: String,
+    },
+    // The rest of the chore's demands carry on after waiting, always at least a frame
+    Wait {
+        frames: usize,
+    },
+    // Drops the rest of every chore that was already waiting
+    CancelSequence,
+    // Entering the state the member is already in does nothing
+    EnterState {
+        state: String,
+    },
+    // Received on the next frame by the named member, or by every member when there isn't one
+    Send {
+        message: String,
+        to: Option<String>,
+    },
+    // Instances only exist while playing and aren't part of history
+    Spawn {
+        template: String,
+        at: JumpLocation,
+    },
+    Destroy,
+    // Spread is in degrees around straight up and lifetime is in frames
+    EmitParticles {
+        style: ParticleStyle,
+        count: u32,
+        speed: Speed,
+        spread: u32,
+        lifetime: u32,
+    },
+    Show,
+    Hide,
+    BringToFront,
+    SendToBack,
+    SetLayer {
+        layer: i32,
+    },
+    // The camera only scrolls once the member leaves the dead zone in the middle of the screen
+    CameraFollow {
+        name: String,
+        dead_zone_width: u32,
+        dead_zone_height: u32,
+        bounds: Option<pixels::Rect>,
+    },
+    CameraPanTo {
+        point: pixels::Position,
+        speed: Speed,
+    },
     PlaySound {
         name: String,
     },
     StopMusic,
     StopSounds,
     Motion(Motion),
+    // Numbers
+    SetNumber {
+        name: String,
+        value: i32,
+    },
+    AddToNumber {
+        name: String,
+        value: i32,
+    },
+    MultiplyNumber {
+        name: String,
+        value: i32,
+    },
+    SetNumberToRandom {
+        name: String,
+        min: i32,
+        max: i32,
+    },
+    SetTextFromNumber {
+        name: String,
+    },
     // Animation?
     SetAnimationSprite,
     AddAnimationSprite,
@@ -280,16 +496,24 @@ pub enum Demand {
     MoveChoreDown,
     MoveQuestionUp,
     MoveQuestionDown,
+    NegateQuestion,
+    GroupQuestionWithNext(Group),
+    UngroupQuestion,
     MoveDemandUp,
     MoveDemandDown,
     UpdateQuestion,
     UpdateDemand,
     SetStartSprite,
+    SetChoreLabel,
+    SetStartState,
+    SetChoreState,
+    ToggleSolid,
     // Menu Actions
     Quit,
     Stop,
     Play,
     Pause,
+    PlayAtDifficulty(DifficultyLevel),
     MoveToGame {
         name: String,
     },
@@ -329,6 +553,11 @@ pub enum Motion {
         point: pixels::Position,
         speed: Speed,
     },
+    FollowPath {
+        points: Vec<pixels::Position>,
+        speed: Speed,
+        looping: PathLooping,
+    },
     JumpTo(JumpLocation),
     Swap {
         name: String,
@@ -347,9 +576,54 @@ pub enum Motion {
         offset: pixels::Position,
         speed: Speed,
     },
+    // Targets whichever tagged member is closest when the demand is made
+    TargetTagged {
+        tag: String,
+        offset: pixels::Position,
+        speed: Speed,
+    },
     AttachFromPositions {
         name: String,
     },
+    // Velocity is in pixels per frame, drag is the fraction of it lost each frame
+    Physics {
+        velocity_x: f32,
+        velocity_y: f32,
+        gravity: f32,
+        drag: f32,
+        bounciness: f32,
+        bounds: Option<pixels::Rect>,
+    },
+    // Starts physics without gravity if the member isn't already moving with it
+    Impulse {
+        x: f32,
+        y: f32,
+    },
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub enum ParticleStyle {
+    Colour(Colour),
+    Sprite(Sprite),
+}
+
+impl Default for ParticleStyle {
+    fn default() -> Self {
+        ParticleStyle::Colour(Colour {
+            r: 1.0,
+            g: 1.0,
+            b: 1.0,
+            a: 1.0,
+        })
+    }
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, EnumString, Default)]
+pub enum PathLooping {
+    #[default]
+    Once,
+    Loop,
+    PingPong,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, EnumString, Default)]
@@ -380,6 +654,11 @@ pub enum When {
         start: usize,
         end: usize,
     },
+    // In frames, unlike Exact
+    Every {
+        interval: usize,
+        offset: usize,
+    },
 }
 
 // TODO: Make relative into Member + offset?
@@ -392,6 +671,10 @@ pub enum JumpLocation {
     Member {
         name: String,
     },
+    // A random member with the tag
+    Tagged {
+        tag: String,
+    },
     Relative {
         offset: pixels::Position,
     },
@@ -475,6 +758,15 @@ pub enum WinStatus {
 pub enum CollisionWith {
     Area(pixels::Rect),
     Member { name: String },
+    Tagged { tag: String, group: Group },
+}
+
+// How many of the members with a tag have to match
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, PartialEq, EnumString)]
+pub enum Group {
+    #[default]
+    Any,
+    All,
 }
 
 impl Default for CollisionWith {
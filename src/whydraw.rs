@@ -22,19 +22,19 @@ use crate::edit::{
     demand_from_context, fancy_demand_text, fancy_demand_text_for_list, fancy_question_text,
     fancy_question_text_for_chore, fancy_question_text_for_list, general_area_size,
     get_typed_variable, index_from_member_text, is_position_in_general_area, max_var_per_page,
-    offset_for_page, padded_len, question_from_context, simple_text, sprite_from_context, Editor,
-    Fancy,
+    offset_for_page, padded_len, path_from_text, question_from_context, simple_text,
+    sprite_from_context, Editor, Fancy,
 };
 
 use crate::inp::{Input, Mouse};
 use crate::meta::{
-    is_screen_member_name, Environment, Transition, CHOOSE_AREA_NAME, CHOOSE_POINT_NAME,
-    EDITABLE_SCREEN_NAME, FADE_LEN, INNER_HEIGHT, INNER_WIDTH, MUSIC_MAKER_NAME, OUTER_CENTRE,
-    OUTER_HEIGHT, OUTER_WIDTH,
+    is_screen_member_name, Environment, Transition, CHOOSE_AREA_NAME, CHOOSE_PATH_NAME,
+    CHOOSE_POINT_NAME, EDITABLE_SCREEN_NAME, FADE_LEN, INNER_HEIGHT, INNER_WIDTH, MUSIC_MAKER_NAME,
+    OUTER_CENTRE, OUTER_HEIGHT, OUTER_WIDTH, PLAY_SCREEN_NAME,
 };
 use crate::pixels;
 use crate::play::{self, is_position_in_sprite_sheet_image};
-use crate::serial::IntroText;
+use crate::serial::{IntroText, ParticleStyle};
 use crate::TEMP_TESTING_INTRO_TEXT;
 use macroquad::{
     color::{colors as quad_colours, Color as Colour},
@@ -538,7 +538,8 @@ impl WhyDrawer {
                 let mut fancy_text: Vec<FancyText> = simple_text(&member.text.contents);
 
                 if member.text.contents.starts_with("{Chore ") {
-                    fancy_text = fancy_question_text_for_chore(member, subgame, editor);
+                    fancy_text =
+                        fancy_question_text_for_chore(member, &game.members, subgame, editor);
                 }
 
                 if member.text.contents == "{Question Stage}" {
@@ -546,8 +547,13 @@ impl WhyDrawer {
                     fancy_text = fancy_question_text(&question);
                     fancy_text.push("?".plain());
                 } else if member.text.contents.starts_with("{Question ") {
-                    fancy_text =
-                        fancy_question_text_for_list(member, subgame, editor, &environment.context);
+                    fancy_text = fancy_question_text_for_list(
+                        member,
+                        &game.members,
+                        subgame,
+                        editor,
+                        &environment.context,
+                    );
                 }
 
                 if member.text.contents == "{Demand Stage}" {
@@ -555,8 +561,13 @@ impl WhyDrawer {
                     fancy_text = fancy_demand_text(&demand);
                     fancy_text.push("!".plain());
                 } else if member.text.contents.starts_with("{Demand ") {
-                    fancy_text =
-                        fancy_demand_text_for_list(member, subgame, editor, &environment.context);
+                    fancy_text = fancy_demand_text_for_list(
+                        member,
+                        &game.members,
+                        subgame,
+                        editor,
+                        &environment.context,
+                    );
                 }
 
                 // TODO: Location?
@@ -686,16 +697,23 @@ impl WhyDrawer {
             if is_screen_member_name(&member.text.contents) {
                 // Temporary
                 self.drawer.clear(inner_camera, colours::WHITE);
-                for member in subgame.members.iter() {
+                // Hidden and out of shot members are still shown while editing
+                let is_playing = member.text.contents == PLAY_SCREEN_NAME;
+                let shot =
+                    pixels::Rect::from_top_left(subgame.scroll(), subgame.size.screen_size());
+                for member in subgame.members_in_drawing_order() {
                     let source = sheet_source_rect(member.sprite);
-                    let params = DrawParams {
-                        source: Some(source),
-                        ..Default::default()
-                    };
+                    let turned_size = member.transform.size(source.size());
+                    let is_out_of_shot = member.sprite.size != SpriteSize::Empty
+                        && !drawn_sized_rect(member.position, turned_size).collides(shot);
+                    if is_playing && (member.hidden || is_out_of_shot) {
+                        continue;
+                    }
+                    let (screen_rect, params) = member_sprite_drawing(member);
 
                     self.drawer.draw_params_texture(
                         inner_camera,
-                        drawn_source_rect(member.position, source),
+                        screen_rect,
                         &subgame.assets.texture,
                         params,
                     );
@@ -765,6 +783,38 @@ impl WhyDrawer {
                     );
                 }
 
+                // Particles go above every member
+                for particle in &subgame.particles {
+                    match particle.style {
+                        ParticleStyle::Colour(colour) => {
+                            let colour =
+                                Colour::new(colour.r, colour.g, colour.b, particle.alpha());
+                            let screen_rect = pixels::Rect::tlwh(
+                                particle.position.x as i32,
+                                particle.position.y as i32,
+                                1,
+                                1,
+                            );
+                            self.drawer
+                                .draw_rectangle(inner_camera, screen_rect, colour);
+                        }
+                        ParticleStyle::Sprite(sprite) => {
+                            let source = sheet_source_rect(sprite);
+                            let mut params = DrawParams {
+                                source: Some(source),
+                                ..Default::default()
+                            };
+                            params.colour.a = particle.alpha();
+                            self.drawer.draw_params_texture(
+                                inner_camera,
+                                drawn_source_rect(particle.position, source),
+                                &subgame.assets.texture,
+                                params,
+                            );
+                        }
+                    }
+                }
+
                 if editor.inner_copy.is_none()
                     && member.text.contents == EDITABLE_SCREEN_NAME
                     && !macroquad::input::is_key_down(KeyCode::LeftShift)
@@ -926,7 +976,10 @@ impl WhyDrawer {
                 let screen_rect =
                     pixels::Rect::aabb(x - offset, y - offset, x + offset, y + offset);
 
-                let sprite = subgame.members[editor.selected_index].sprite;
+                let sprite = subgame
+                    .members
+                    .get(editor.selected_index)
+                    .map_or(Sprite::none(), |member| member.sprite);
                 let source = sheet_source_rect(sprite);
                 let params = DrawParams {
                     source: Some(source),
@@ -948,23 +1001,47 @@ impl WhyDrawer {
                 self.drawer
                     .draw_params_rectangle(inner_camera, screen_rect, params);
             }
+            if member.text.contents == CHOOSE_PATH_NAME {
+                let points = path_from_text(
+                    environment
+                        .context
+                        .get("Path")
+                        .map(String::as_str)
+                        .unwrap_or_default(),
+                );
+
+                for pair in points.windows(2) {
+                    self.drawer
+                        .draw_line(inner_camera, pair[0], pair[1], colours::RED);
+                }
+
+                let offset = 1;
+                for point in points {
+                    let screen_rect = pixels::Rect::aabb(
+                        point.x - offset,
+                        point.y - offset,
+                        point.x + offset,
+                        point.y + offset,
+                    );
+                    self.drawer
+                        .draw_rectangle(inner_camera, screen_rect, colours::RED);
+                }
+            }
         }
 
         if let Transition::FadeOut { .. } = transition {
-            for member in &subgame.members {
-                let source = sheet_source_rect(member.sprite);
-
-                let params = DrawParams {
-                    source: Some(source),
-                    ..Default::default()
-                };
+            for member in subgame.members_in_drawing_order() {
+                if member.hidden {
+                    continue;
+                }
+                let (screen_rect, params) = member_sprite_drawing(member);
 
                 // TODO: Temporary
                 //params.colour = colours::AMBER;
 
                 self.drawer.draw_params_texture(
                     inner_camera,
-                    drawn_source_rect(member.position, source),
+                    screen_rect,
                     &subgame.assets.texture,
                     params,
                 );
@@ -984,7 +1061,10 @@ impl WhyDrawer {
                 let alpha = *fade_left as f32 * fade_step;
                 let scale = Some((1.0 - alpha) * 3.0 + 1.0);
 
-                for member in &fade_game.members {
+                for member in fade_game.members_in_drawing_order() {
+                    if member.hidden {
+                        continue;
+                    }
                     let source = sheet_source_rect(member.sprite);
 
                     let mut params = DrawParams {
@@ -1618,6 +1698,52 @@ impl WhyDrawer {
                 }
             }
         }
+
+        if let Some(error) = game.error.as_ref().or(subgame.error.as_ref()) {
+            self.draw_error_overlay(error);
+        }
+    }
+
+    fn draw_error_overlay(&mut self, error: &str) {
+        let mut colour = colours::BLACK;
+        colour.a = 0.8;
+        let screen_rect = pixels::Rect::from_top_left(
+            pixels::Position::new(0, 0),
+            pixels::Size::new(OUTER_WIDTH, OUTER_HEIGHT),
+        );
+        self.drawer
+            .draw_rectangle(Camera::Outer, screen_rect, colour);
+
+        let max_width = OUTER_WIDTH - 32;
+        let mut lines = vec!["Something went wrong".to_string(), "".to_string()];
+        let mut line = String::new();
+        for word in error.split_whitespace() {
+            let candidate = if line.is_empty() {
+                word.to_string()
+            } else {
+                format!("{} {}", line, word)
+            };
+            if !line.is_empty() && self.intro_font.text_width(&candidate) > max_width {
+                lines.push(std::mem::replace(&mut line, word.to_string()));
+            } else {
+                line = candidate;
+            }
+        }
+        lines.push(line);
+        lines.push("".to_string());
+        lines.push("Click to continue".to_string());
+
+        let line_height = self.intro_font.char_height as f32 + 2.0;
+        let top = OUTER_CENTRE.y as f32 - line_height * lines.len() as f32 / 2.0;
+        for (i, line) in lines.iter().enumerate() {
+            self.drawer.draw_bitmap_text(
+                Camera::Outer,
+                Vec2::new(OUTER_CENTRE.x as f32, top + line_height * (i as f32 + 0.5)),
+                line,
+                colours::WHITE,
+                &self.intro_font,
+            );
+        }
     }
 
     pub fn draw_sprite_sheet(
@@ -1751,3 +1877,18 @@ impl WhyDrawer {
         }
     }
 }
+
+// Where a member's sprite goes once it's scaled, and how it's flipped, turned and tinted
+fn member_sprite_drawing(member: &play::Member) -> (pixels::Rect, DrawParams) {
+    let source = sheet_source_rect(member.sprite);
+    let size = source.size() * member.transform.scale as f32;
+    let params = DrawParams {
+        colour: member.tint.unwrap_or(quad_colours::WHITE),
+        source: Some(source),
+        flip_x: member.transform.flip_x,
+        flip_y: member.transform.flip_y,
+        rotation: member.transform.rotation.radians(),
+        ..Default::default()
+    };
+    (drawn_sized_rect(member.position, size), params)
+}